@@ -1,7 +1,7 @@
 // Apply ActionEffects and config overlays produced by Lua actions.
 //
 // - `apply_effects` handles transient UI state (selection, overlays, output,
-//   redraw, quit).
+//   refresh, redraw, quit).
 // - `apply_config_overlay` applies validated, structural changes coming from
 //   Lua (panes, theme, sort, etc.), computing minimal relist/redraw.
 use super::effects::{
@@ -73,6 +73,10 @@ pub fn apply_effects(
     {
         app.display_output(&title, &text);
     }
+    if let Some(code) = fx.exit_code
+    {
+        app.last_exit = Some(code);
+    }
     if let Some(msg) = fx.message_text.as_ref()
     {
         app.add_message(msg);
@@ -95,6 +99,15 @@ pub fn apply_effects(
     {
         app.start_preview_process(cmd);
     }
+    for cmd in fx.os_run_async.iter()
+    {
+        let cwd = app.command_dir();
+        match app.jobs.spawn(cmd, cwd)
+        {
+            Ok(()) => app.add_message(&format!("Started: $ {}", cmd)),
+            Err(e) => app.add_message(&format!("Error: $ {}: {}", cmd, e)),
+        }
+    }
 
     match fx.theme_picker
     {
@@ -142,6 +155,12 @@ pub fn apply_effects(
             );
             app.request_delete_selected();
         }
+        crate::actions::effects::ConfirmCommand::Ask { question, action } =>
+        {
+            crate::core::overlays::request_confirm_action(
+                app, question, action,
+            );
+        }
         crate::actions::effects::ConfirmCommand::None =>
         {}
     }
@@ -180,6 +199,15 @@ pub fn apply_effects(
         {
             app.clear_clipboard()
         }
+        crate::actions::effects::ClipboardCommand::YankPath =>
+        {
+            app.yank_paths(false)
+        }
+        crate::actions::effects::ClipboardCommand::YankRelPath =>
+        {
+            app.yank_paths(true)
+        }
+        crate::actions::effects::ClipboardCommand::YankName => app.yank_names(),
         crate::actions::effects::ClipboardCommand::None =>
         {}
     }
@@ -208,14 +236,53 @@ pub fn apply_effects(
         {}
     }
 
+    if let Some(line) = fx.open_line
+    {
+        app.open_selected_at_line(line);
+    }
+    if let Some(name) = fx.touch.as_deref()
+    {
+        if name.is_empty()
+        {
+            app.touch_selected();
+        }
+        else
+        {
+            app.touch_named(name);
+        }
+    }
+    if fx.toggle_hidden
+    {
+        app.toggle_hidden();
+    }
+    if fx.refresh
+    {
+        let current_name = app.selected_entry().map(|e| e.name.clone());
+        app.refresh_lists();
+        if let Some(name) = current_name
+        {
+            crate::core::selection::reselect_by_name(app, &name);
+        }
+        app.refresh_preview();
+    }
+    // After any refresh so the revealed entry is selected last
+    if let Some(path) = fx.reveal.as_deref()
+    {
+        app.reveal_path(path);
+    }
     if fx.redraw
     {
         app.force_full_redraw = true;
     }
-    if fx.quit
+    if fx.quit && fx.quit_discard
     {
+        app.discard_state = true;
         app.should_quit = true;
     }
+    else if fx.quit
+    {
+        app.request_quit();
+    }
 }
 
 // Apply a validated config overlay to the App, computing the minimal
@@ -287,6 +354,13 @@ pub fn apply_config_overlay(
         app.config.ui.confirm_delete = data.ui.confirm_delete;
     }
 
+    // Scrolloff: render only
+    if app.config.ui.scrolloff != data.ui.scrolloff
+    {
+        app.config.ui.scrolloff = data.ui.scrolloff;
+        redraw_only = true;
+    }
+
     // Max list items: impacts listing
     if app.config.ui.max_list_items != data.ui.max_list_items
     {
@@ -351,9 +425,22 @@ pub fn apply_config_overlay(
             hidden_bg:             th.hidden_bg.clone(),
             exec_fg:               th.exec_fg.clone(),
             exec_bg:               th.exec_bg.clone(),
+            denied_fg:             th.denied_fg.clone(),
+            symlink_fg:            th.symlink_fg.clone(),
+            symlink_broken_fg:     th.symlink_broken_fg.clone(),
             selection_bar_fg:      th.selection_bar_fg.clone(),
             selection_bar_copy_fg: th.selection_bar_copy_fg.clone(),
             selection_bar_move_fg: th.selection_bar_move_fg.clone(),
+            selection_bar_copy_bg: th.selection_bar_copy_bg.clone(),
+            selection_bar_move_bg: th.selection_bar_move_bg.clone(),
+            clipboard_copy_glyph:  th.clipboard_copy_glyph.clone(),
+            clipboard_move_glyph:  th.clipboard_move_glyph.clone(),
+            item_mods:             th.item_mods.clone(),
+            selected_item_mods:    th.selected_item_mods.clone(),
+            dir_mods:              th.dir_mods.clone(),
+            file_mods:             th.file_mods.clone(),
+            hidden_mods:           th.hidden_mods.clone(),
+            exec_mods:             th.exec_mods.clone(),
         };
         Some(t)
     }
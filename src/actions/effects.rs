@@ -15,7 +15,10 @@ pub struct ActionEffects
 {
     pub selection:       Option<usize>,
     pub quit:            bool,
+    pub quit_discard:    bool,
     pub redraw:          bool,
+    pub refresh:         bool,
+    pub toggle_hidden:   bool,
     pub messages:        OverlayToggle,
     pub output_overlay:  OverlayToggle,
     pub output:          Option<(String, String)>, // (title, text)
@@ -32,6 +35,15 @@ pub struct ActionEffects
     pub select_paths:    Option<Vec<String>>,
     pub clear_messages:  bool,
     pub preview_run_cmd: Option<String>,
+    pub exit_code:       Option<i32>,
+    pub open_line:       Option<usize>,
+    // Some("") touches the selection; otherwise the named path
+    pub touch:           Option<String>,
+    pub reveal:          Option<String>,
+    // Commands for lsv.os_run_async, in call order
+    pub os_run_async:    Vec<String>,
+    // The action returned `false` (an `lsv.on_enter` hook declining)
+    pub declined:        bool,
 }
 use mlua::Table;
 
@@ -83,10 +95,33 @@ pub fn parse_effects_from_lua(tbl: &Table) -> ActionEffects
     {
         fx.error_text = Some(e);
     }
+    if let Ok(code) = tbl.get::<i32>("exit_code")
+    {
+        fx.exit_code = Some(code);
+    }
+    if let Ok(line) = tbl.get::<u64>("open_line")
+    {
+        fx.open_line = Some(line as usize);
+    }
+    if let Ok(path) = tbl.get::<String>("reveal")
+    {
+        fx.reveal = Some(path);
+    }
+    if let Ok(cmds) = tbl.get::<Table>("os_run_async")
+    {
+        fx.os_run_async = cmds.sequence_values::<String>().flatten().collect();
+    }
+    if let Ok(name) = tbl.get::<String>("touch")
+    {
+        fx.touch = Some(name.trim().to_string());
+    }
     fx.clear_messages = tbl.get::<bool>("clear_messages").unwrap_or(false);
-    // redraw/quit
+    fx.toggle_hidden = tbl.get::<bool>("toggle_hidden").unwrap_or(false);
+    // refresh/redraw/quit
+    fx.refresh = tbl.get::<bool>("refresh").unwrap_or(false);
     fx.redraw = tbl.get::<bool>("redraw").unwrap_or(false);
     fx.quit = tbl.get::<bool>("quit").unwrap_or(false);
+    fx.quit_discard = tbl.get::<bool>("quit_discard").unwrap_or(false);
     if let Ok(tp) = tbl.get::<String>("theme_picker")
         && tp == "open"
     {
@@ -152,6 +187,16 @@ pub fn parse_effects_from_lua(tbl: &Table) -> ActionEffects
                 );
                 fx.confirm = ConfirmCommand::DeleteSelected;
             }
+            "ask" =>
+            {
+                if let (Ok(question), Ok(action)) = (
+                    tbl.get::<String>("confirm_question"),
+                    tbl.get::<String>("confirm_action"),
+                ) && !action.trim().is_empty()
+                {
+                    fx.confirm = ConfirmCommand::Ask { question, action };
+                }
+            }
             _ =>
             {}
         }
@@ -194,6 +239,9 @@ pub fn parse_effects_from_lua(tbl: &Table) -> ActionEffects
             "move_arm" => ClipboardCommand::MoveArm,
             "paste" => ClipboardCommand::Paste,
             "clear" => ClipboardCommand::Clear,
+            "yank_path" => ClipboardCommand::YankPath,
+            "yank_relpath" => ClipboardCommand::YankRelPath,
+            "yank_name" => ClipboardCommand::YankName,
             _ => ClipboardCommand::None,
         };
     }
@@ -218,12 +266,17 @@ pub enum PromptCommand
     OpenRenameEntry,
 }
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub enum ConfirmCommand
 {
     #[default]
     None,
     DeleteSelected,
+    Ask
+    {
+        question: String,
+        action:   String,
+    },
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -244,6 +297,9 @@ pub enum ClipboardCommand
     MoveArm,
     Paste,
     Clear,
+    YankPath,
+    YankRelPath,
+    YankName,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -8,12 +8,15 @@ pub enum SortKey
     Size,
     MTime,
     CTime,
+    ATime,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum InternalAction
 {
     Quit,
+    QuitForce,
+    QuitDiscard,
     Sort(SortKey),
     ToggleSortReverse,
     SetInfo(crate::app::InfoMode),
@@ -24,6 +27,14 @@ pub(crate) enum InternalAction
     NavDown,
     NavParent,
     NavEnter,
+    NavFirstDir,
+    NavLastDir,
+    NavFirstFile,
+    NavLastFile,
+    NavFollowSymlink,
+    NavParentUp,
+    NavParentDown,
+    GotoPath,
     MarksAddWait,
     MarksGotoWait,
     RunCommand(String),
@@ -31,7 +42,49 @@ pub(crate) enum InternalAction
     ClipboardMove,
     ClipboardPaste,
     ClipboardClear,
+    RegisterCopy(char),
+    RegisterPaste(char),
     CloseOverlays,
+    TogglePreview,
+    TogglePreviewWrap,
+    PreviewRefresh,
+    TraceOpen,
+    ConfigReload,
+    TreeShow,
+    Open,
+    OpenAtLine(usize),
+    ToggleFlatView,
+    YankPath,
+    YankRelPath,
+    YankName,
+    YankSize,
+    YankDirname,
+    YankContents,
+    Duplicate,
+    Filter(crate::core::filter::FilterKind, Option<String>),
+    FilterClear,
+    SelectAll,
+    SelectNone,
+    SelectInvert,
+    Touch(Option<String>),
+    TouchPrompt,
+    NewFromTemplate,
+    TransferTo(crate::app::ClipboardOp, Option<String>),
+    GotoLine(Option<String>),
+    NewNote,
+    ConfigSetStartDir,
+    ConfigShowPaths,
+    ReportCounts,
+    ReportSelectionSize,
+    ToggleInfoColumn,
+    ToggleDirSlash,
+    ToggleHidden,
+    ToggleSearchCase,
+    ReloadTheme,
+    TabNew,
+    TabClose,
+    TabNext,
+    TabPrev,
 }
 
 pub(crate) fn parse_internal_action(s: &str) -> Option<InternalAction>
@@ -41,6 +94,15 @@ pub(crate) fn parse_internal_action(s: &str) -> Option<InternalAction>
     {
         return Some(InternalAction::Quit);
     }
+    if low == "quit:force"
+    {
+        return Some(InternalAction::QuitForce);
+    }
+    // Both bang forms discard, as in `:q!`
+    if low == "quit:discard" || low == "quit!" || low == "q!"
+    {
+        return Some(InternalAction::QuitDiscard);
+    }
     if low == "sort:reverse:toggle" || low == "sort:rev:toggle"
     {
         return Some(InternalAction::ToggleSortReverse);
@@ -87,6 +149,30 @@ pub(crate) fn parse_internal_action(s: &str) -> Option<InternalAction>
     {
         return Some(InternalAction::GoBottom);
     }
+    if low == "goto:path"
+    {
+        return Some(InternalAction::GotoPath);
+    }
+    if let Some(rest) = low.strip_prefix("nav:goto_line")
+        && (rest.is_empty() || rest.starts_with(char::is_whitespace))
+    {
+        let n = rest.trim();
+        return Some(InternalAction::GotoLine(
+            (!n.is_empty()).then(|| n.to_string()),
+        ));
+    }
+    if low == "nav:follow_symlink"
+    {
+        return Some(InternalAction::NavFollowSymlink);
+    }
+    if low == "nav:parent_up"
+    {
+        return Some(InternalAction::NavParentUp);
+    }
+    if low == "nav:parent_down"
+    {
+        return Some(InternalAction::NavParentDown);
+    }
     if low == "nav:up"
     {
         return Some(InternalAction::NavUp);
@@ -95,7 +181,12 @@ pub(crate) fn parse_internal_action(s: &str) -> Option<InternalAction>
     {
         return Some(InternalAction::NavDown);
     }
-    if low == "nav:parent" || low == "nav:left"
+    // `nav:up` already moves the selection, so the parent jump is `nav:updir`
+    if low == "nav:parent"
+        || low == "nav:leave"
+        || low == "nav:left"
+        || low == "nav:updir"
+        || low == ".."
     {
         return Some(InternalAction::NavParent);
     }
@@ -103,6 +194,22 @@ pub(crate) fn parse_internal_action(s: &str) -> Option<InternalAction>
     {
         return Some(InternalAction::NavEnter);
     }
+    if low == "nav:first_dir"
+    {
+        return Some(InternalAction::NavFirstDir);
+    }
+    if low == "nav:last_dir"
+    {
+        return Some(InternalAction::NavLastDir);
+    }
+    if low == "nav:first_file"
+    {
+        return Some(InternalAction::NavFirstFile);
+    }
+    if low == "nav:last_file"
+    {
+        return Some(InternalAction::NavLastFile);
+    }
     if low == "marks:add_wait" || low == "marks:add"
     {
         return Some(InternalAction::MarksAddWait);
@@ -131,13 +238,251 @@ pub(crate) fn parse_internal_action(s: &str) -> Option<InternalAction>
     {
         return Some(InternalAction::ClipboardClear);
     }
+    // Register names keep their case: `register:copy A` differs from `a`
+    if let Some((verb, reg)) = register_args(s.trim())
+    {
+        return match verb.as_str()
+        {
+            "copy" => Some(InternalAction::RegisterCopy(reg)),
+            "paste" => Some(InternalAction::RegisterPaste(reg)),
+            _ => None,
+        };
+    }
     if low == "overlay:close"
     {
         return Some(InternalAction::CloseOverlays);
     }
+    if low == "preview:toggle"
+    {
+        return Some(InternalAction::TogglePreview);
+    }
+    if low == "preview:toggle_wrap"
+    {
+        return Some(InternalAction::TogglePreviewWrap);
+    }
+    if low == "preview:refresh"
+    {
+        return Some(InternalAction::PreviewRefresh);
+    }
+    if low == "trace:open"
+    {
+        return Some(InternalAction::TraceOpen);
+    }
+    if low == "config:reload"
+    {
+        return Some(InternalAction::ConfigReload);
+    }
+    if low == "clipboard:yank_path"
+    {
+        return Some(InternalAction::YankPath);
+    }
+    if low == "clipboard:yank_relpath" || low == "yank:relpath"
+    {
+        return Some(InternalAction::YankRelPath);
+    }
+    if low == "clipboard:yank_name" || low == "yank:name"
+    {
+        return Some(InternalAction::YankName);
+    }
+    if low == "yank:size"
+    {
+        return Some(InternalAction::YankSize);
+    }
+    if low == "clipboard:yank_contents" || low == "yank:contents"
+    {
+        return Some(InternalAction::YankContents);
+    }
+    if low == "yank:dirname"
+    {
+        return Some(InternalAction::YankDirname);
+    }
+    if low == "file:touch_prompt"
+    {
+        return Some(InternalAction::TouchPrompt);
+    }
+    if low == "file:new_from_template"
+    {
+        return Some(InternalAction::NewFromTemplate);
+    }
+    // `file:touch <name>` keeps the name's case
+    if let Some(rest) = low.strip_prefix("file:touch")
+        && (rest.is_empty() || rest.starts_with(char::is_whitespace))
+    {
+        let name = s.trim()["file:touch".len()..].trim();
+        return Some(InternalAction::Touch(
+            (!name.is_empty()).then(|| name.to_string()),
+        ));
+    }
+    // `file:copy_to <dir>` / `file:move_to <dir>` keep the directory's case;
+    // without one they prompt
+    for (prefix, op) in [
+        ("file:copy_to", crate::app::ClipboardOp::Copy),
+        ("file:move_to", crate::app::ClipboardOp::Move),
+    ]
+    {
+        if let Some(rest) = low.strip_prefix(prefix)
+            && (rest.is_empty() || rest.starts_with(char::is_whitespace))
+        {
+            let dir = s.trim()[prefix.len()..].trim();
+            return Some(InternalAction::TransferTo(
+                op,
+                (!dir.is_empty()).then(|| dir.to_string()),
+            ));
+        }
+    }
+    if low == "file:duplicate"
+    {
+        return Some(InternalAction::Duplicate);
+    }
+    for (prefix, kind) in [
+        ("filter:glob", crate::core::filter::FilterKind::Glob),
+        ("filter:regex", crate::core::filter::FilterKind::Regex),
+    ]
+    {
+        if let Some(rest) = low.strip_prefix(prefix)
+            && (rest.is_empty() || rest.starts_with(char::is_whitespace))
+        {
+            let pattern = s.trim()[prefix.len()..].trim();
+            return Some(InternalAction::Filter(
+                kind,
+                (!pattern.is_empty()).then(|| pattern.to_string()),
+            ));
+        }
+    }
+    if low == "filter:clear"
+    {
+        return Some(InternalAction::FilterClear);
+    }
+    if low == "select:all"
+    {
+        return Some(InternalAction::SelectAll);
+    }
+    if low == "select:none"
+    {
+        return Some(InternalAction::SelectNone);
+    }
+    if low == "select:invert"
+    {
+        return Some(InternalAction::SelectInvert);
+    }
+    if low == "new:note"
+    {
+        return Some(InternalAction::NewNote);
+    }
+    if low == "flat:toggle"
+    {
+        return Some(InternalAction::ToggleFlatView);
+    }
+    if low == "open"
+    {
+        return Some(InternalAction::Open);
+    }
+    if let Some(rest) = low.strip_prefix("open:line")
+        && (rest.is_empty() || rest.starts_with(char::is_whitespace))
+    {
+        let line = rest.trim().parse::<usize>().unwrap_or(1);
+        return Some(InternalAction::OpenAtLine(line));
+    }
+    if low == "tree:show"
+    {
+        return Some(InternalAction::TreeShow);
+    }
+    if low == "config:set_start_dir"
+    {
+        return Some(InternalAction::ConfigSetStartDir);
+    }
+    if low == "config:show_paths"
+    {
+        return Some(InternalAction::ConfigShowPaths);
+    }
+    if low == "toggle:info_column"
+    {
+        return Some(InternalAction::ToggleInfoColumn);
+    }
+    if low == "toggle:dir_slash"
+    {
+        return Some(InternalAction::ToggleDirSlash);
+    }
+    if low == "filter:toggle_case" || low == "find:toggle_case"
+    {
+        return Some(InternalAction::ToggleSearchCase);
+    }
+    if low == "toggle:hidden"
+    {
+        return Some(InternalAction::ToggleHidden);
+    }
+    if low == "theme:reload"
+    {
+        return Some(InternalAction::ReloadTheme);
+    }
+    if low == "info:counts"
+    {
+        return Some(InternalAction::ReportCounts);
+    }
+    if low == "info:selection_size"
+    {
+        return Some(InternalAction::ReportSelectionSize);
+    }
+    if low == "tab:new"
+    {
+        return Some(InternalAction::TabNew);
+    }
+    if low == "tab:close"
+    {
+        return Some(InternalAction::TabClose);
+    }
+    if low == "tab:next"
+    {
+        return Some(InternalAction::TabNext);
+    }
+    if low == "tab:prev"
+    {
+        return Some(InternalAction::TabPrev);
+    }
     None
 }
 
+/// Split `register:<verb> <c>` into its lowercased verb and register char.
+fn register_args(s: &str) -> Option<(String, char)>
+{
+    let (head, arg) = s.split_once(char::is_whitespace)?;
+    let verb = head.to_ascii_lowercase().strip_prefix("register:")?.to_string();
+    let mut chars = arg.trim().chars();
+    let reg = chars.next()?;
+    if chars.next().is_some()
+    {
+        return None;
+    }
+    Some((verb, reg))
+}
+
+/// Index of the first or last directory/file entry for the `nav:first_dir`
+/// family of actions.
+fn kind_position(
+    app: &crate::app::App,
+    action: &InternalAction,
+) -> Option<usize>
+{
+    let want_dir = matches!(
+        action,
+        InternalAction::NavFirstDir | InternalAction::NavLastDir
+    );
+    let mut matching = app
+        .current_entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.is_dir == want_dir)
+        .map(|(i, _)| i);
+    match action
+    {
+        InternalAction::NavLastDir | InternalAction::NavLastFile =>
+        {
+            matching.next_back()
+        }
+        _ => matching.next(),
+    }
+}
+
 pub(crate) fn execute_internal_action(
     app: &mut crate::app::App,
     action: InternalAction,
@@ -152,6 +497,15 @@ pub(crate) fn execute_internal_action(
     {
         InternalAction::Quit =>
         {
+            app.request_quit();
+        }
+        InternalAction::QuitForce =>
+        {
+            app.should_quit = true;
+        }
+        InternalAction::QuitDiscard =>
+        {
+            app.discard_state = true;
             app.should_quit = true;
         }
         InternalAction::Sort(key) =>
@@ -217,6 +571,17 @@ pub(crate) fn execute_internal_action(
             }
             app.force_full_redraw = true;
         }
+        InternalAction::NavFirstDir
+        | InternalAction::NavLastDir
+        | InternalAction::NavFirstFile
+        | InternalAction::NavLastFile =>
+        {
+            if let Some(idx) = kind_position(app, &action)
+            {
+                app.list_state.select(Some(idx));
+                app.refresh_preview();
+            }
+        }
         InternalAction::GoTop =>
         {
             if !app.current_entries.is_empty()
@@ -261,40 +626,11 @@ pub(crate) fn execute_internal_action(
         }
         InternalAction::NavEnter =>
         {
-            if let Some(entry) = app.selected_entry()
-                && entry.is_dir
-            {
-                app.cwd = entry.path.clone();
-                app.refresh_lists();
-                if app.current_entries.is_empty()
-                {
-                    app.list_state.select(None);
-                }
-                else
-                {
-                    app.list_state.select(Some(0));
-                }
-                app.refresh_preview();
-            }
+            app.nav_enter();
         }
         InternalAction::NavParent =>
         {
-            if let Some(parent) = app.cwd.parent()
-            {
-                let just_left = app
-                    .cwd
-                    .file_name()
-                    .map(|s| s.to_string_lossy().to_string());
-                app.cwd = parent.to_path_buf();
-                app.refresh_lists();
-                if let Some(name) = just_left
-                    && let Some(idx) =
-                        app.current_entries.iter().position(|e| e.name == name)
-                {
-                    app.list_state.select(Some(idx));
-                }
-                app.refresh_preview();
-            }
+            app.go_parent();
         }
         InternalAction::MarksAddWait =>
         {
@@ -326,11 +662,226 @@ pub(crate) fn execute_internal_action(
         {
             app.clear_all_selected();
         }
+        InternalAction::RegisterCopy(reg) =>
+        {
+            app.copy_to_register(reg);
+        }
+        InternalAction::RegisterPaste(reg) =>
+        {
+            app.paste_register(reg);
+        }
         InternalAction::CloseOverlays =>
         {
             app.overlay = crate::app::Overlay::None;
             app.force_full_redraw = true;
         }
+        InternalAction::TogglePreview =>
+        {
+            app.toggle_preview_pane();
+        }
+        InternalAction::TogglePreviewWrap =>
+        {
+            app.toggle_preview_wrap();
+        }
+        InternalAction::PreviewRefresh =>
+        {
+            app.force_refresh_preview();
+        }
+        InternalAction::TraceOpen =>
+        {
+            app.open_trace_log();
+        }
+        InternalAction::ConfigReload =>
+        {
+            app.reload_config();
+        }
+        InternalAction::YankPath =>
+        {
+            app.yank_paths(false);
+        }
+        InternalAction::YankRelPath =>
+        {
+            app.yank_paths(true);
+        }
+        InternalAction::YankName =>
+        {
+            app.yank_names();
+        }
+        InternalAction::YankSize =>
+        {
+            app.yank_size();
+        }
+        InternalAction::YankDirname =>
+        {
+            app.yank_dirname();
+        }
+        InternalAction::YankContents =>
+        {
+            app.yank_contents();
+        }
+        InternalAction::Duplicate =>
+        {
+            app.duplicate_selected();
+        }
+        InternalAction::Filter(kind, None) =>
+        {
+            crate::core::overlays::open_filter_prompt(app, kind);
+        }
+        InternalAction::Filter(kind, Some(pattern)) =>
+        {
+            app.set_list_filter(kind, &pattern);
+        }
+        InternalAction::FilterClear =>
+        {
+            app.clear_list_filter();
+        }
+        InternalAction::SelectAll =>
+        {
+            app.select_all_visible();
+        }
+        InternalAction::SelectNone =>
+        {
+            app.clear_all_selected();
+            app.force_full_redraw = true;
+        }
+        InternalAction::SelectInvert =>
+        {
+            app.invert_selection();
+        }
+        InternalAction::Touch(None) =>
+        {
+            app.touch_selected();
+        }
+        InternalAction::Touch(Some(name)) =>
+        {
+            app.touch_named(&name);
+        }
+        InternalAction::TouchPrompt =>
+        {
+            crate::core::overlays::open_touch_prompt(app);
+        }
+        InternalAction::NewFromTemplate =>
+        {
+            app.open_template_picker();
+        }
+        InternalAction::GotoLine(None) =>
+        {
+            crate::core::overlays::open_goto_line_prompt(app);
+        }
+        InternalAction::GotoLine(Some(n)) =>
+        {
+            app.goto_line(&n);
+        }
+        InternalAction::TransferTo(op, None) =>
+        {
+            crate::core::overlays::open_transfer_to_prompt(app, op);
+        }
+        InternalAction::TransferTo(op, Some(dir)) =>
+        {
+            app.transfer_selected_to(&dir, op);
+        }
+        InternalAction::NewNote =>
+        {
+            app.new_note();
+        }
+        InternalAction::ToggleFlatView =>
+        {
+            app.toggle_flat_view();
+        }
+        InternalAction::Open =>
+        {
+            app.open_selected();
+        }
+        InternalAction::NavFollowSymlink =>
+        {
+            app.follow_symlink();
+        }
+        InternalAction::NavParentUp =>
+        {
+            app.move_parent_cursor(-1);
+        }
+        InternalAction::NavParentDown =>
+        {
+            app.move_parent_cursor(1);
+        }
+        InternalAction::GotoPath =>
+        {
+            crate::core::overlays::open_goto_path_prompt(app);
+        }
+        InternalAction::OpenAtLine(line) =>
+        {
+            app.open_selected_at_line(line);
+        }
+        InternalAction::TreeShow =>
+        {
+            app.show_tree();
+        }
+        InternalAction::ConfigSetStartDir =>
+        {
+            app.set_start_dir();
+        }
+        InternalAction::ConfigShowPaths =>
+        {
+            match crate::config::config_paths_report(&app.cwd)
+            {
+                Ok(text) => app.display_output("Config paths", &text),
+                Err(e) =>
+                {
+                    app.add_message(&format!("Config paths: {}", e));
+                }
+            }
+        }
+        InternalAction::ToggleDirSlash =>
+        {
+            app.config.ui.dir_slash = !app.config.ui.dir_slash;
+            app.force_full_redraw = true;
+        }
+        InternalAction::ToggleHidden =>
+        {
+            app.toggle_hidden();
+        }
+        InternalAction::ToggleSearchCase =>
+        {
+            app.toggle_search_case();
+        }
+        InternalAction::ReloadTheme =>
+        {
+            if app.reload_theme()
+                && let Some(path) = app.config.ui.theme_path.as_ref()
+            {
+                let msg = format!("Theme: reloaded {}", path.display());
+                app.add_message(&msg);
+            }
+        }
+        InternalAction::ToggleInfoColumn =>
+        {
+            app.show_info_column = !app.show_info_column;
+            app.force_full_redraw = true;
+        }
+        InternalAction::ReportCounts =>
+        {
+            app.report_counts();
+        }
+        InternalAction::ReportSelectionSize =>
+        {
+            app.report_selection_size();
+        }
+        InternalAction::TabNew =>
+        {
+            app.new_tab();
+        }
+        InternalAction::TabClose =>
+        {
+            app.close_tab();
+        }
+        InternalAction::TabNext =>
+        {
+            app.cycle_tab(1);
+        }
+        InternalAction::TabPrev =>
+        {
+            app.cycle_tab(-1);
+        }
     }
 }
 
@@ -419,6 +970,15 @@ pub(crate) fn internal_effects(
                 Some(ActionEffects::default())
             }
         }
+        InternalAction::NavFirstDir
+        | InternalAction::NavLastDir
+        | InternalAction::NavFirstFile
+        | InternalAction::NavLastFile =>
+        {
+            let idx = kind_position(app, action);
+            let fx = ActionEffects { selection: idx, ..Default::default() };
+            Some(fx)
+        }
         InternalAction::MarksAddWait =>
         {
             let fx = ActionEffects {
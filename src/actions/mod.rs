@@ -7,3 +7,20 @@ pub mod internal;
 
 pub use dispatcher::dispatch_action;
 pub(crate) use internal::SortKey;
+
+/// Error raised when a user-requested action (e.g. a Lua action) fails.
+#[derive(Debug)]
+pub struct ActionError(pub String);
+
+impl std::fmt::Display for ActionError
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result
+    {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ActionError {}
@@ -18,6 +18,7 @@ use crate::actions::SortKey;
 pub(crate) mod state;
 pub use state::{
     App,
+    CaseMode,
     Clipboard,
     ClipboardOp,
     CommandPaneState,
@@ -33,6 +34,8 @@ pub use state::{
     PromptKind,
     PromptState,
     RunningPreview,
+    TabState,
+    TemplatePickerState,
     ThemePickerEntry,
     ThemePickerState,
 };
@@ -44,83 +47,58 @@ pub(crate) mod nav;
 pub(crate) mod overlays_api;
 pub(crate) mod preview_ctrl;
 pub(crate) mod selection;
+pub(crate) mod tabs;
 
 // Re-exported types live in state.rs
 
 impl App
 {
+    /// Construct an [`App`] in the startup directory recorded by
+    /// `config:set_start_dir`, falling back to the current working directory
+    /// when none is recorded or it no longer exists.
+    pub fn new_at_start_dir() -> io::Result<Self>
+    {
+        let recorded = crate::config::discover_config_paths()
+            .ok()
+            .and_then(|p| {
+                crate::core::marks::load_start_dir(&p.root.join("start_dir"))
+            })
+            .filter(|d| d.is_dir());
+        if let Some(dir) = recorded
+        {
+            env::set_current_dir(&dir)?;
+        }
+        Self::new()
+    }
+
+    /// Entries of `dir`, hidden ones included, directories first and then
+    /// by name.
+    fn initial_entries(dir: &std::path::Path) -> io::Result<Vec<DirEntryInfo>>
+    {
+        let mut entries: Vec<DirEntryInfo> = fs::read_dir(dir)?
+            .flatten()
+            .filter_map(|de| crate::core::listing::entry_info(&de, true, true))
+            .collect();
+        entries.sort_by(|a, b| match (a.is_dir, b.is_dir)
+        {
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        });
+        Ok(entries)
+    }
+
     /// Construct a fresh [`App`] using the current working directory as the
     /// starting point.
     pub fn new() -> io::Result<Self>
     {
         let cwd = env::current_dir()?;
-        // Temporary initial read with default sort (Name asc)
-        let current_entries = {
-            // Build a temporary App-like context for sorting
-            let mut tmp = Vec::new();
-            for de in (fs::read_dir(&cwd)?).flatten()
-            {
-                let path = de.path();
-                let name = de.file_name().to_string_lossy().to_string();
-                if let Ok(ft) = de.file_type()
-                {
-                    let meta = fs::metadata(&path).ok();
-                    let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
-                    let mtime = meta.as_ref().and_then(|m| m.modified().ok());
-                    let ctime = meta.as_ref().and_then(|m| m.created().ok());
-                    tmp.push(DirEntryInfo {
-                        name,
-                        path,
-                        is_dir: ft.is_dir(),
-                        size,
-                        mtime,
-                        ctime,
-                    });
-                }
-            }
-            tmp.sort_by(|a, b| match (a.is_dir, b.is_dir)
-            {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-            });
-            tmp
-        };
-        let parent_entries = if let Some(p) = cwd.parent()
+        // Initial read with default sort (Name asc)
+        let current_entries = Self::initial_entries(&cwd)?;
+        let parent_entries = match cwd.parent()
         {
-            // Same initial read for parent
-            let mut tmp = Vec::new();
-            for de in (fs::read_dir(p)?).flatten()
-            {
-                let path = de.path();
-                let name = de.file_name().to_string_lossy().to_string();
-                if let Ok(ft) = de.file_type()
-                {
-                    let meta = fs::metadata(&path).ok();
-                    let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
-                    let mtime = meta.as_ref().and_then(|m| m.modified().ok());
-                    let ctime = meta.as_ref().and_then(|m| m.created().ok());
-                    tmp.push(DirEntryInfo {
-                        name,
-                        path,
-                        is_dir: ft.is_dir(),
-                        size,
-                        mtime,
-                        ctime,
-                    });
-                }
-            }
-            tmp.sort_by(|a, b| match (a.is_dir, b.is_dir)
-            {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-            });
-            tmp
-        }
-        else
-        {
-            Vec::new()
+            Some(p) => Self::initial_entries(p)?,
+            None => Vec::new(),
         };
 
         let mut list_state = ListState::default();
@@ -133,27 +111,65 @@ impl App
             current_entries,
             parent_entries,
             list_state,
+            parent_list_state: ListState::default(),
+            parent_cursor: None,
+            // Until the first draw: terminal height less header and borders
+            visible_rows: crossterm::terminal::size()
+                .map(|(_, h)| h.saturating_sub(3) as usize)
+                .unwrap_or(20)
+                .max(1),
             preview: PreviewState::default(),
             recent_messages: Vec::new(),
+            messages_scroll: 0,
             overlay: Overlay::None,
             config: crate::config::Config::default(),
             keys: KeyState::default(),
             force_full_redraw: false,
             lua: None,
             selected: std::collections::HashSet::new(),
+            visual_anchor: None,
             clipboard: None,
             sort_key: SortKey::Name,
             sort_reverse: false,
             info_mode: InfoMode::None,
             display_mode: DisplayMode::Absolute,
             should_quit: false,
+            discard_state: false,
+            marks_dirty: false,
             search_query: None,
             _search_locked: false,
             marks: std::collections::HashMap::new(),
             pending_mark: false,
             pending_goto: false,
             running_preview: None,
+            saved_panes: None,
+            watcher: None,
+            watch_failed: None,
+            listing_stream: None,
+            list_filter: None,
+            theme_watcher: None,
+            theme_watch_failed: None,
+            screen_mode: crate::runtime::ScreenMode::Alternate,
+            dir_sizes: crate::core::dir_sizes::DirSizeCache::default(),
+            jobs: crate::core::jobs::Jobs::default(),
+            entry_counts: crate::core::entry_counts::EntryCountCache::default(),
+            selection_size:
+                crate::core::selection_size::SelectionSizeCache::default(),
+            preview_pending_since: None,
+            preview_wrap: true,
+            flat_view: false,
+            spinner_tick: 0,
+            search_case: CaseMode::Smart,
+            tabs: Vec::new(),
+            active_tab: 0,
+            registers: std::collections::HashMap::new(),
+            show_info_column: true,
+            last_exit: None,
+            dir_selection: std::collections::HashMap::new(),
+            last_yank: None,
+            config_error: None,
         };
+        app.tabs.push(app.snapshot_tab());
         // Load marks from config root
         if let Some(root) = app.theme_root_dir()
         {
@@ -165,58 +181,18 @@ impl App
         {
             match crate::config::load_config(&paths)
             {
-                Ok((cfg, maps, engine_opt)) =>
+                Ok(artifacts) =>
                 {
-                    app.config = cfg;
-                    app.keys.maps = maps;
-                    app.rebuild_keymap_lookup();
-                    if let Some((eng, key, action_keys)) = engine_opt
-                    {
-                        app.lua = Some(LuaRuntime {
-                            engine:    eng,
-                            previewer: Some(key),
-                            actions:   action_keys,
-                        });
-                    }
-                    else
-                    {
-                        app.lua = None;
-                    }
-                    // Re-apply lists to honor config (e.g., show_hidden)
-                    // Also apply optional initial sort/show from config.ui
-                    if let Some(ref srt) = app.config.ui.sort
-                        && let Some(k) = crate::enums::sort_key_from_str(srt)
-                    {
-                        app.sort_key = k;
-                    }
-                    if let Some(b) = app.config.ui.sort_reverse
-                    {
-                        app.sort_reverse = b;
-                    }
-                    if let Some(ref sh) = app.config.ui.show
-                    {
-                        if sh.eq_ignore_ascii_case("none")
-                        {
-                            app.info_mode = crate::app::InfoMode::None;
-                        }
-                        else if let Some(m) =
-                            crate::enums::info_mode_from_str(sh)
-                        {
-                            app.info_mode = m;
-                        }
-                    }
-                    app.refresh_lists();
-                    // Apply display_mode from config if present
-                    if let Some(dm) = app.config.ui.display_mode.as_deref()
-                        && let Some(mode) =
-                            crate::enums::display_mode_from_str(dm)
-                    {
-                        app.display_mode = mode;
-                    }
+                    app.install_config(artifacts);
                 }
                 Err(e) =>
                 {
+                    // stderr is hidden by the alternate screen, so open the
+                    // messages overlay with the error on the first draw
                     eprintln!("lsv: config load error: {}", e);
+                    app.config_error = Some(e.to_string());
+                    app.add_message(&format!("Config load error: {}", e));
+                    app.overlay = Overlay::Messages;
                 }
             }
         }
@@ -224,6 +200,105 @@ impl App
         Ok(app)
     }
 
+    /// Swap in a freshly loaded configuration: config values, keymaps and
+    /// the Lua engine. Initial sort/show/display settings from `config.ui`
+    /// are applied and the listing is refreshed.
+    pub fn install_config(
+        &mut self,
+        artifacts: crate::config::ConfigArtifacts,
+    )
+    {
+        let (cfg, maps, engine_opt) = artifacts;
+        self.config = cfg;
+        self.preview_wrap = self.config.ui.preview_wrap;
+        self.keys.maps = maps;
+        self.rebuild_keymap_lookup();
+        if let Some((eng, key, action_keys)) = engine_opt
+        {
+            self.lua = Some(LuaRuntime {
+                engine:    eng,
+                previewer: Some(key),
+                actions:   action_keys,
+            });
+        }
+        else
+        {
+            self.lua = None;
+        }
+        // Re-apply lists to honor config (e.g., show_hidden)
+        // Also apply optional initial sort/show from config.ui
+        if let Some(ref srt) = self.config.ui.sort
+            && let Some(k) = crate::enums::sort_key_from_str(srt)
+        {
+            self.sort_key = k;
+        }
+        if let Some(b) = self.config.ui.sort_reverse
+        {
+            self.sort_reverse = b;
+        }
+        if let Some(m) = self
+            .config
+            .ui
+            .search_case
+            .as_deref()
+            .and_then(crate::enums::case_mode_from_str)
+        {
+            self.search_case = m;
+        }
+        if let Some(ref sh) = self.config.ui.show
+        {
+            if sh.eq_ignore_ascii_case("none")
+            {
+                self.info_mode = crate::app::InfoMode::None;
+            }
+            else if let Some(m) = crate::enums::info_mode_from_str(sh)
+            {
+                self.info_mode = m;
+            }
+        }
+        self.refresh_lists();
+        // Apply display_mode from config if present
+        if let Some(dm) = self.config.ui.display_mode.as_deref()
+            && let Some(mode) = crate::enums::display_mode_from_str(dm)
+        {
+            self.display_mode = mode;
+        }
+    }
+
+    /// Re-run config discovery and loading, replacing the active config,
+    /// keymaps and Lua engine. On failure the current config is kept and the
+    /// error is shown in the messages overlay. The cwd and selection are
+    /// preserved.
+    pub(crate) fn reload_config(&mut self)
+    {
+        let loaded = crate::config::discover_config_paths()
+            .and_then(|paths| crate::config::load_config(&paths));
+        match loaded
+        {
+            Ok(artifacts) =>
+            {
+                let current_name =
+                    self.selected_entry().map(|e| e.name.clone());
+                self.install_config(artifacts);
+                self.config_error = None;
+                if let Some(name) = current_name
+                {
+                    crate::core::selection::reselect_by_name(self, &name);
+                }
+                self.refresh_preview();
+                self.add_message("Config reloaded");
+            }
+            Err(e) =>
+            {
+                crate::trace::log(format!("[config] reload failed: {}", e));
+                self.config_error = Some(e.to_string());
+                self.add_message(&format!("Config reload failed: {}", e));
+                self.overlay = Overlay::Messages;
+            }
+        }
+        self.force_full_redraw = true;
+    }
+
     fn find_match_from(
         &self,
         start: usize,
@@ -235,7 +310,7 @@ impl App
         {
             return None;
         }
-        let pat_l = pat.to_lowercase();
+        let case = self.search_case;
         let len = self.current_entries.len();
         if backwards
         {
@@ -243,7 +318,7 @@ impl App
             for _ in 0..len
             {
                 if let Some(e) = self.current_entries.get(idx)
-                    && e.name.to_lowercase().contains(&pat_l)
+                    && case.matches(&e.name, pat)
                 {
                     return Some(idx);
                 }
@@ -263,7 +338,7 @@ impl App
             for _ in 0..len
             {
                 if let Some(e) = self.current_entries.get(idx)
-                    && e.name.to_lowercase().contains(&pat_l)
+                    && case.matches(&e.name, pat)
                 {
                     return Some(idx);
                 }
@@ -371,10 +446,66 @@ impl App
     {
         self.list_state.selected()
     }
+    /// Paths marked for bulk operations, sorted.
+    pub fn selected_paths(&self) -> Vec<PathBuf>
+    {
+        let mut paths: Vec<PathBuf> = self.selected.iter().cloned().collect();
+        paths.sort();
+        paths
+    }
+    /// Error from the most recent config load, if it failed.
+    pub fn read_config_error(&self) -> Option<&str>
+    {
+        self.config_error.as_deref()
+    }
     pub fn get_quit(&self) -> bool
     {
         self.should_quit
     }
+
+    /// Outstanding work that quitting now would lose, one line per item:
+    /// armed clipboard moves and `lsv.os_run_async` commands still running.
+    /// `os_run`/`os_run_interactive` and pastes complete before control
+    /// returns.
+    pub(crate) fn quit_blockers(&self) -> Vec<String>
+    {
+        let mut out = Vec::new();
+        if self.jobs.is_busy()
+        {
+            out.push(format!(
+                "{} background command(s) still running",
+                self.jobs.running()
+            ));
+        }
+        if let Some(cb) = self.clipboard.as_ref()
+            && matches!(cb.op, ClipboardOp::Move)
+            && !cb.items.is_empty()
+        {
+            out.push(format!(
+                "{} item(s) armed to move but not pasted",
+                cb.items.len()
+            ));
+        }
+        out
+    }
+
+    /// Quit, or ask first when [`App::quit_blockers`] reports pending work.
+    /// `quit:force` skips the check.
+    pub(crate) fn request_quit(&mut self)
+    {
+        let blockers = self.quit_blockers();
+        if blockers.is_empty()
+        {
+            self.should_quit = true;
+            return;
+        }
+        let question = format!("Quit anyway? {}.", blockers.join("; "));
+        crate::core::overlays::request_confirm_action(
+            self,
+            question,
+            String::from("quit:force"),
+        );
+    }
     pub fn get_sort_reverse(&self) -> bool
     {
         self.sort_reverse
@@ -418,21 +549,63 @@ impl App
     {
         self.config.clone()
     }
+    pub fn set_screen_mode(
+        &mut self,
+        mode: crate::runtime::ScreenMode,
+    )
+    {
+        self.screen_mode = mode;
+    }
+    pub fn get_screen_mode(&self) -> crate::runtime::ScreenMode
+    {
+        self.screen_mode
+    }
     pub fn get_cwd_path(&self) -> std::path::PathBuf
     {
         self.cwd.clone()
     }
 
+    /// Current pane split as `(parent, current, preview)` percentages.
+    pub fn get_pane_percentages(&self) -> Option<(u16, u16, u16)>
+    {
+        self.config.ui.panes.as_ref().map(|p| (p.parent, p.current, p.preview))
+    }
+
+    pub fn get_preview_wrap(&self) -> bool
+    {
+        self.preview_wrap
+    }
+
     pub fn preview_line_count(&self) -> usize
     {
         self.preview.static_lines.len()
     }
 
+    pub fn get_last_yank(&self) -> Option<&str>
+    {
+        self.last_yank.as_deref()
+    }
+
+    pub fn get_last_exit(&self) -> Option<i32>
+    {
+        self.last_exit
+    }
+
+    pub fn get_preview_lines(&self) -> &[String]
+    {
+        &self.preview.static_lines
+    }
+
     pub fn recent_messages_len(&self) -> usize
     {
         self.recent_messages.len()
     }
 
+    pub fn last_message(&self) -> Option<&str>
+    {
+        self.recent_messages.last().map(String::as_str)
+    }
+
     pub fn add_message(
         &mut self,
         msg: &str,
@@ -452,8 +625,27 @@ impl App
         self.force_full_redraw = true;
     }
 
+    /// Scroll the messages overlay by `delta` rows; positive values move
+    /// towards older messages. The draw step clamps to what fits on screen.
+    pub fn scroll_messages(
+        &mut self,
+        delta: isize,
+    )
+    {
+        let max = self.recent_messages.len().saturating_sub(1);
+        self.messages_scroll =
+            self.messages_scroll.saturating_add_signed(delta).min(max);
+        self.force_full_redraw = true;
+    }
+
+    pub fn messages_scroll(&self) -> usize
+    {
+        self.messages_scroll
+    }
+
     pub fn clear_recent_messages(&mut self)
     {
+        self.messages_scroll = 0;
         if !self.recent_messages.is_empty()
         {
             self.recent_messages.clear();
@@ -522,6 +714,7 @@ impl App
                     {
                         self.config.ui.theme = Some(theme);
                         self.config.ui.theme_path = Some(path.clone());
+                        self.config.ui.theme_overlay = None;
                         self.force_full_redraw = true;
                         return true;
                     }
@@ -540,6 +733,40 @@ impl App
         false
     }
 
+    /// Re-read the current `ui.theme_path` and apply it, keeping the inline
+    /// `ui.theme` overlay on top. On errors the current theme is kept.
+    pub fn reload_theme(&mut self) -> bool
+    {
+        let Some(path) = self.config.ui.theme_path.clone()
+        else
+        {
+            self.add_message("Theme: no theme file to reload");
+            return false;
+        };
+        match crate::config::load_theme_from_file(&path)
+        {
+            Ok(mut theme) =>
+            {
+                if let Some(top) = self.config.ui.theme_overlay.as_ref()
+                {
+                    theme.overlay(top);
+                }
+                self.config.ui.theme = Some(theme);
+                self.force_full_redraw = true;
+                true
+            }
+            Err(e) =>
+            {
+                self.add_message(&format!(
+                    "Theme: failed to reload {} ({})",
+                    path.display(),
+                    e
+                ));
+                false
+            }
+        }
+    }
+
     pub(crate) fn theme_root_dir(&self) -> Option<PathBuf>
     {
         crate::config::discover_config_paths().ok().map(|p| p.root)
@@ -575,6 +802,19 @@ impl App
         matches!(self.overlay, Overlay::ThemePicker(_))
     }
 
+    pub(crate) fn template_picker_move(
+        &mut self,
+        delta: isize,
+    )
+    {
+        crate::core::overlays::template_picker_move(self, delta)
+    }
+
+    pub(crate) fn confirm_template_picker(&mut self)
+    {
+        crate::core::overlays::confirm_template_picker(self)
+    }
+
     pub fn display_output(
         &mut self,
         title: &str,
@@ -63,7 +63,7 @@ impl App
                 }
                 if removed > 0
                 {
-                    self.save_marks();
+                    self.marks_changed();
                 }
                 self.add_message(&format!("Deleted {} mark(s)", removed));
             }
@@ -92,6 +92,10 @@ impl App
                 self.force_full_redraw = true;
             }
             "change_theme" | "theme" => self.open_theme_picker(),
+            "reload_theme" =>
+            {
+                self.reload_theme();
+            }
             "add_item" | "add" => self.open_add_entry_prompt(),
             "rename_selected" | "rename" => self.open_rename_entry_prompt(),
             "delete_selected" | "delete" => self.request_delete_selected(),
@@ -100,17 +104,22 @@ impl App
                 self.toggle_select_current()
             }
             "clear_selected" | "select_clear" => self.clear_all_selected(),
+            "visual_select" | "visual" => self.toggle_visual_select(),
             "toggle_hidden_files" | "show_hidden_toggle" =>
             {
-                self.config.ui.show_hidden = !self.config.ui.show_hidden;
-                self.refresh_lists();
-                self.refresh_preview();
+                self.toggle_hidden()
+            }
+            "toggle_octal_permissions" | "octal_perms" =>
+            {
+                self.config.ui.permissions_octal =
+                    !self.config.ui.permissions_octal;
                 self.force_full_redraw = true;
             }
             "sort_name" => self.execute_command_line("sort name"),
             "sort_size" => self.execute_command_line("sort size"),
             "sort_modified_date" => self.execute_command_line("sort mtime"),
             "sort_created_date" => self.execute_command_line("sort created"),
+            "sort_accessed_date" => self.execute_command_line("sort atime"),
             "sort" =>
             {
                 if let Some(arg) = parts.next()
@@ -267,4 +276,24 @@ impl App
             }
         }
     }
+
+    /// Cycle the find/filter case mode (smart-case, case-sensitive,
+    /// case-insensitive), re-run the active find pattern from the cursor and
+    /// re-apply the active filter.
+    pub(crate) fn toggle_search_case(&mut self)
+    {
+        self.search_case = self.search_case.next();
+        if let Some(q) = self.search_query.clone()
+        {
+            self.update_search_live(&q);
+        }
+        if let Some(f) = self.list_filter.take()
+        {
+            self.set_list_filter(f.kind, &f.pattern);
+        }
+        self.add_message(&format!(
+            "Find: {}",
+            crate::enums::case_mode_to_str(self.search_case)
+        ));
+    }
 }
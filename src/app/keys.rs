@@ -12,9 +12,9 @@ impl App
         self.keys.prefixes.clear();
         for m in &self.keys.maps
         {
-            self.keys.lookup.insert(m.sequence.clone(), m.action.clone());
             // collect token-based prefixes for sequence matching
             let tokens = tokenize_sequence(&m.sequence);
+            self.keys.lookup.insert(tokens.concat(), m.action.clone());
             let mut acc = String::new();
             for (idx, t) in tokens.iter().enumerate()
             {
@@ -27,6 +27,37 @@ impl App
         }
     }
 
+    /// How long the `pending` key sequence may wait for its next key:
+    /// the longest timeout among mappings that extend it, each using its own
+    /// `timeout_ms` or the global `keys.sequence_timeout_ms`. `None` means it
+    /// never expires.
+    pub(crate) fn sequence_timeout(
+        &self,
+        pending: &str,
+    ) -> Option<std::time::Duration>
+    {
+        let global = self.config.keys.sequence_timeout_ms;
+        let mut longest: Option<u64> = None;
+        for m in &self.keys.maps
+        {
+            let seq = tokenize_sequence(&m.sequence).concat();
+            if seq.len() > pending.len() && seq.starts_with(pending)
+            {
+                let ms = m.timeout_ms.unwrap_or(global);
+                if ms == 0
+                {
+                    return None;
+                }
+                longest = Some(longest.map_or(ms, |l| l.max(ms)));
+            }
+        }
+        match longest.unwrap_or(global)
+        {
+            0 => None,
+            ms => Some(std::time::Duration::from_millis(ms)),
+        }
+    }
+
     pub fn set_keymaps(
         &mut self,
         maps: Vec<crate::config::KeyMapping>,
@@ -41,7 +72,7 @@ impl App
         seq: &str,
     ) -> Option<String>
     {
-        self.keys.lookup.get(seq).cloned()
+        self.keys.lookup.get(&tokenize_sequence(seq).concat()).cloned()
     }
 
     pub fn has_prefix(
@@ -49,6 +80,6 @@ impl App
         seq: &str,
     ) -> bool
     {
-        self.keys.prefixes.contains(seq)
+        self.keys.prefixes.contains(&tokenize_sequence(seq).concat())
     }
 }
@@ -1,15 +1,40 @@
-//! Marks persistence and navigation for App.
+//! Marks persistence and navigation for App, plus the pinned startup
+//! directory. Mark changes are written as they happen; one that could not be
+//! written is retried when lsv exits (see [`App::flush_state`]).
 
 use crate::app::App;
 
 impl App
 {
-    pub(crate) fn save_marks(&self)
+    /// Write the marks file. False when the write failed.
+    pub(crate) fn save_marks(&self) -> bool
     {
-        if let Some(root) = self.theme_root_dir()
+        let Some(root) = self.theme_root_dir()
+        else
+        {
+            return true;
+        };
+        crate::core::marks::save_marks(&root.join("marks"), &self.marks).is_ok()
+    }
+
+    /// Write the marks through after a change, leaving them dirty for the
+    /// exit-time flush when the write failed.
+    pub(crate) fn marks_changed(&mut self)
+    {
+        self.marks_dirty = !self.save_marks();
+    }
+
+    /// Write session state not yet on disk (marks whose write failed) on
+    /// exit, unless the app was quit with `quit:discard`.
+    pub fn flush_state(&mut self)
+    {
+        if self.discard_state
+        {
+            return;
+        }
+        if self.marks_dirty
         {
-            let path = root.join("marks");
-            let _ = crate::core::marks::save_marks(&path, &self.marks);
+            self.marks_dirty = !self.save_marks();
         }
     }
 
@@ -20,7 +45,7 @@ impl App
     {
         let dir = self.cwd.clone();
         self.marks.insert(ch, dir.clone());
-        self.save_marks();
+        self.marks_changed();
         self.add_message(&format!("Mark '{}' set: {}", ch, dir.display()));
     }
 
@@ -51,6 +76,27 @@ impl App
         }
     }
 
+    /// Record the cwd as the directory lsv opens in when launched without a
+    /// directory argument.
+    pub(crate) fn set_start_dir(&mut self)
+    {
+        let Some(root) = self.theme_root_dir()
+        else
+        {
+            self.add_message("Start dir: no config directory");
+            return;
+        };
+        let dir = self.cwd.clone();
+        match crate::core::marks::save_start_dir(&root.join("start_dir"), &dir)
+        {
+            Ok(()) =>
+            {
+                self.add_message(&format!("Start dir set: {}", dir.display()))
+            }
+            Err(e) => self.add_message(&format!("Start dir: {}", e)),
+        }
+    }
+
     pub(crate) fn list_marks_text(&self) -> String
     {
         let mut keys: Vec<char> = self.marks.keys().copied().collect();
@@ -16,8 +16,188 @@ use crate::{
     },
 };
 
+/// The user's editor from `$VISUAL` or `$EDITOR`, if either is set.
+fn editor_from_env() -> Option<String>
+{
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|k| std::env::var(k).ok())
+        .find(|v| !v.trim().is_empty())
+}
+
 impl App
 {
+    /// Open the selected file with the configured opener for its extension
+    /// (falling back to `openers.default`). The command runs with the
+    /// terminal handed over, like `lsv.os_run_interactive`. Directories are
+    /// entered instead, as with `nav:enter`.
+    pub(crate) fn open_selected(&mut self)
+    {
+        let Some(entry) = self.selected_entry().cloned()
+        else
+        {
+            return;
+        };
+        if entry.is_dir
+        {
+            self.nav_enter();
+            return;
+        }
+        let ext = entry
+            .path
+            .extension()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let Some(template) = self
+            .config
+            .openers
+            .get(&ext)
+            .or_else(|| self.config.openers.get("default"))
+            .cloned()
+        else
+        {
+            self.add_message(&format!("No opener for {}", entry.name));
+            return;
+        };
+        self.run_opener(&template, &entry.path);
+    }
+
+    /// Create today's note (named by `ui.note_template`, default
+    /// `%Y-%m-%d.md`) in `ui.notes_dir` or the cwd, then open it in
+    /// `$VISUAL`/`$EDITOR`, falling back to the configured openers. An
+    /// existing note is opened as is.
+    pub(crate) fn new_note(&mut self)
+    {
+        let template =
+            self.config.ui.note_template.as_deref().unwrap_or("%Y-%m-%d.md");
+        let dir = self
+            .config
+            .ui
+            .notes_dir
+            .clone()
+            .unwrap_or_else(|| self.cwd.clone());
+        let (path, created) = match crate::core::fs_ops::ensure_note(
+            &dir,
+            template,
+            chrono::Local::now(),
+        )
+        {
+            Ok(v) => v,
+            Err(e) =>
+            {
+                self.add_message(&format!("Note: {}", e));
+                return;
+            }
+        };
+        if created && path.parent() == Some(self.cwd.as_path())
+        {
+            self.refresh_lists();
+            if let Some(name) = path.file_name()
+            {
+                crate::core::selection::reselect_by_name(
+                    self,
+                    &name.to_string_lossy(),
+                );
+            }
+            self.refresh_preview();
+        }
+        let editor = editor_from_env().map(|e| format!("{} {{path}}", e));
+        let ext = path
+            .extension()
+            .map(|s| s.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let template = editor.or_else(|| {
+            self.config
+                .openers
+                .get(&ext)
+                .or_else(|| self.config.openers.get("default"))
+                .cloned()
+        });
+        match template
+        {
+            Some(t) => self.run_opener(&t, &path),
+            None => self.add_message(&format!(
+                "Note {}: {} (no editor configured)",
+                if created { "created" } else { "exists" },
+                path.display()
+            )),
+        }
+    }
+
+    /// Open the selected file in `$VISUAL`/`$EDITOR` (else `vi`) at `line`,
+    /// using the argument form that editor expects.
+    pub(crate) fn open_selected_at_line(
+        &mut self,
+        line: usize,
+    )
+    {
+        let Some(entry) = self.selected_entry().cloned()
+        else
+        {
+            return;
+        };
+        if entry.is_dir
+        {
+            self.add_message(&format!("{} is a directory", entry.name));
+            return;
+        }
+        let editor = editor_from_env().unwrap_or_else(|| "vi".to_string());
+        let template = crate::util::editor_line_command(&editor, line.max(1));
+        self.run_opener(&template, &entry.path);
+    }
+
+    /// Run an opener command template for `path` with the terminal handed
+    /// over, recording its exit status.
+    fn run_opener(
+        &mut self,
+        template: &str,
+        path: &Path,
+    )
+    {
+        let cmd = crate::util::expand_path_placeholders(template, path);
+        crate::trace::log(format!("[open] cmd='{}'", cmd));
+        let status = crate::config::runtime::glue::run_interactive(
+            &cmd,
+            &self.command_dir().to_string_lossy(),
+            self.screen_mode.uses_alternate(),
+        );
+        self.last_exit =
+            Some(status.as_ref().map_or(-1, |s| s.code().unwrap_or(-1)));
+        match status
+        {
+            Ok(s) if s.success() => self.add_message(&format!("$ {}", cmd)),
+            Ok(s) => self.add_message(&format!(
+                "$ {} (exit status: {})",
+                cmd,
+                s.code()
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "signal".into())
+            )),
+            Err(e) => self.add_message(&format!("$ {}: {}", cmd, e)),
+        }
+        self.force_full_redraw = true;
+    }
+
+    /// Working directory for shell commands: the cwd, or with
+    /// `ui.command_cwd = "selection"` the directory containing the selected
+    /// entry (which differs from the cwd in flat view).
+    pub(crate) fn command_dir(&self) -> std::path::PathBuf
+    {
+        let use_selection = self
+            .config
+            .ui
+            .command_cwd
+            .as_deref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("selection"));
+        if use_selection
+            && let Some(parent) =
+                self.selected_entry().and_then(|e| e.path.parent())
+        {
+            return parent.to_path_buf();
+        }
+        self.cwd.clone()
+    }
+
     pub(crate) fn selected_entry(&self) -> Option<&DirEntryInfo>
     {
         self.list_state.selected().and_then(|i| self.current_entries.get(i))
@@ -40,10 +220,95 @@ impl App
         self.refresh_preview();
     }
 
+    /// Keep only entries whose names match `pattern`. An invalid pattern is
+    /// reported and the listing is left as it was.
+    pub(crate) fn set_list_filter(
+        &mut self,
+        kind: crate::core::filter::FilterKind,
+        pattern: &str,
+    )
+    {
+        let label = match kind
+        {
+            crate::core::filter::FilterKind::Glob => "glob",
+            crate::core::filter::FilterKind::Regex => "regex",
+        };
+        let sensitive = self.search_case.is_sensitive(pattern);
+        match crate::core::filter::ListFilter::new(kind, pattern, sensitive)
+        {
+            Ok(filter) =>
+            {
+                let current_name =
+                    self.selected_entry().map(|e| e.name.clone());
+                self.list_filter = Some(filter);
+                self.refresh_lists();
+                if let Some(name) = current_name
+                {
+                    crate::core::selection::reselect_by_name(self, &name);
+                }
+                self.refresh_preview();
+                self.add_message(&format!(
+                    "Filter: {} {} ({} matching)",
+                    label,
+                    pattern,
+                    self.current_entries.len()
+                ));
+            }
+            Err(e) =>
+            {
+                self.add_message(&format!(
+                    "Filter: invalid {} '{}': {}",
+                    label, pattern, e
+                ));
+            }
+        }
+        self.force_full_redraw = true;
+    }
+
+    /// Drop the active name filter and restore the full listing. Returns
+    /// false when no filter was set.
+    pub(crate) fn clear_list_filter(&mut self) -> bool
+    {
+        if self.list_filter.take().is_none()
+        {
+            return false;
+        }
+        let current_name = self.selected_entry().map(|e| e.name.clone());
+        self.refresh_lists();
+        if let Some(name) = current_name
+        {
+            crate::core::selection::reselect_by_name(self, &name);
+        }
+        self.refresh_preview();
+        self.force_full_redraw = true;
+        true
+    }
+
     pub(crate) fn refresh_lists(&mut self)
     {
-        self.current_entries =
-            self.read_dir_sorted(&self.cwd).unwrap_or_default();
+        // Any listing still streaming in belongs to the old state
+        self.listing_stream = None;
+        self.visual_anchor = None;
+        self.current_entries = if self.flat_view
+        {
+            self.read_dir_flat(&self.cwd).unwrap_or_default()
+        }
+        else
+        {
+            match self.read_dir_streaming(&self.cwd)
+            {
+                Ok((entries, stream)) =>
+                {
+                    self.listing_stream = stream;
+                    entries
+                }
+                Err(_) => Vec::new(),
+            }
+        };
+        if let Some(filter) = self.list_filter.as_ref()
+        {
+            self.current_entries.retain(|e| filter.matches(&e.name));
+        }
         if self.current_entries.len() > self.config.ui.max_list_items
         {
             self.current_entries.truncate(self.config.ui.max_list_items);
@@ -101,20 +366,346 @@ impl App
         )
     }
 
-    pub fn set_cwd(
+    /// Read the current directory's first page; large directories continue
+    /// loading in the background (see [`crate::core::listing::poll`]).
+    fn read_dir_streaming(
+        &self,
+        path: &Path,
+    ) -> io::Result<(
+        Vec<DirEntryInfo>,
+        Option<crate::core::listing::ListingStream>,
+    )>
+    {
+        let need_meta = !matches!(self.info_mode, InfoMode::None)
+            || !matches!(self.sort_key, SortKey::Name);
+        crate::core::listing::read_dir_streaming(
+            path,
+            self.config.ui.show_hidden,
+            self.sort_key,
+            self.sort_reverse,
+            need_meta,
+            self.config.ui.max_list_items,
+            crate::core::listing::FIRST_PAGE,
+        )
+    }
+
+    fn read_dir_flat(
+        &self,
+        path: &Path,
+    ) -> io::Result<Vec<DirEntryInfo>>
+    {
+        let need_meta = !matches!(self.info_mode, InfoMode::None)
+            || !matches!(self.sort_key, SortKey::Name);
+        crate::core::listing::read_dir_flat(
+            path,
+            self.config.ui.show_hidden,
+            self.sort_key,
+            self.sort_reverse,
+            need_meta,
+            self.config.ui.max_list_items,
+        )
+    }
+
+    /// Switch the current pane between the normal listing and a flat,
+    /// recursive listing of every file under the cwd.
+    pub(crate) fn toggle_flat_view(&mut self)
+    {
+        let current = self.selected_entry().map(|e| e.path.clone());
+        self.flat_view = !self.flat_view;
+        self.refresh_lists();
+        let idx = current.and_then(|p| {
+            self.current_entries.iter().position(|e| e.path == p)
+        });
+        if let Some(i) = idx
+        {
+            self.list_state.select(Some(i));
+        }
+        self.refresh_preview();
+        self.add_message(
+            if self.flat_view { "Flat view on" } else { "Flat view off" },
+        );
+        self.force_full_redraw = true;
+    }
+
+    /// Flip `ui.show_hidden` and relist, keeping the selected entry when it
+    /// is still listed.
+    pub(crate) fn toggle_hidden(&mut self)
+    {
+        let current_name = self.selected_entry().map(|e| e.name.clone());
+        self.config.ui.show_hidden = !self.config.ui.show_hidden;
+        self.refresh_lists();
+        if let Some(name) = current_name
+        {
+            crate::core::selection::reselect_by_name(self, &name);
+        }
+        self.refresh_preview();
+        self.force_full_redraw = true;
+    }
+
+    /// Report how many directories and files are listed and how many hidden
+    /// entries the cwd holds, whether or not they are shown.
+    pub(crate) fn report_counts(&mut self)
+    {
+        let dirs = self.current_entries.iter().filter(|e| e.is_dir).count();
+        let files = self.current_entries.len() - dirs;
+        let hidden = crate::core::listing::count_hidden(&self.cwd).unwrap_or(0);
+        let state =
+            if self.config.ui.show_hidden { "shown" } else { "not shown" };
+        self.add_message(&format!(
+            "{} dirs, {} files listed; {} hidden ({})",
+            dirs, files, hidden, state
+        ));
+    }
+
+    /// Record the selected entry of the cwd so it can be restored when the
+    /// directory is entered again.
+    fn remember_selection(&mut self)
+    {
+        if let Some(name) = self.selected_entry().map(|e| e.name.clone())
+        {
+            self.dir_selection.insert(self.cwd.clone(), name);
+        }
+    }
+
+    /// Enter the selected directory (`nav:enter`). An `lsv.on_enter` hook
+    /// runs first with the directory as `config.context.current_file`;
+    /// returning `false` (or failing) keeps the current directory.
+    pub(crate) fn nav_enter(&mut self)
+    {
+        let Some(path) =
+            self.selected_entry().filter(|e| e.is_dir).map(|e| e.path.clone())
+        else
+        {
+            return;
+        };
+        if let Some(idx) = self.config.on_enter
+        {
+            match crate::config::runtime::glue::call_lua_action(self, idx)
+            {
+                Ok((fx, overlay)) =>
+                {
+                    let declined = fx.declined;
+                    crate::actions::apply::apply_effects(self, fx);
+                    if let Some(data) = overlay
+                    {
+                        crate::actions::apply::apply_config_overlay(
+                            self, &data,
+                        );
+                    }
+                    if declined || self.should_quit
+                    {
+                        return;
+                    }
+                }
+                Err(e) =>
+                {
+                    self.add_message(&format!("on_enter: {}", e));
+                    return;
+                }
+            }
+        }
+        self.enter_dir(&path);
+    }
+
+    /// Change to `path`, reselecting the entry that was selected when it was
+    /// last left (or the first entry).
+    pub(crate) fn enter_dir(
+        &mut self,
+        path: &Path,
+    )
+    {
+        self.enter_dir_selecting(path, None);
+    }
+
+    /// Change to `path`, selecting `prefer` if present, else the remembered
+    /// entry, else the first entry.
+    pub(crate) fn enter_dir_selecting(
         &mut self,
         path: &Path,
+        prefer: Option<String>,
     )
     {
+        self.remember_selection();
         self.cwd = path.to_path_buf();
+        self.list_state.select(None);
         self.refresh_lists();
-        if !self.current_entries.is_empty()
+        let wanted =
+            prefer.or_else(|| self.dir_selection.get(&self.cwd).cloned());
+        let idx = if self.current_entries.is_empty() { None } else { Some(0) };
+        self.list_state.select(idx);
+        if let Some(name) = wanted
         {
-            self.list_state.select(Some(0));
-            self.refresh_preview();
+            crate::core::selection::reselect_by_name(self, &name);
+        }
+        self.refresh_preview();
+    }
+
+    /// Change to the parent directory, selecting the directory just left.
+    pub(crate) fn go_parent(&mut self)
+    {
+        let Some(parent) = self.cwd.parent().map(Path::to_path_buf)
+        else
+        {
+            return;
+        };
+        let just_left =
+            self.cwd.file_name().map(|s| s.to_string_lossy().to_string());
+        self.enter_dir_selecting(&parent, just_left);
+    }
+
+    /// Jump to the directory holding the selected symlink's resolved target,
+    /// selecting the target. Non-links and broken links only report.
+    pub(crate) fn follow_symlink(&mut self)
+    {
+        let Some(entry) = self.selected_entry().cloned()
+        else
+        {
+            return;
+        };
+        let is_link = std::fs::symlink_metadata(&entry.path)
+            .is_ok_and(|m| m.file_type().is_symlink());
+        if !is_link
+        {
+            self.add_message(&format!("{} is not a symlink", entry.name));
+            return;
+        }
+        let target = match std::fs::canonicalize(&entry.path)
+        {
+            Ok(t) => t,
+            Err(e) =>
+            {
+                let dest = std::fs::read_link(&entry.path)
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|_| "?".into());
+                self.add_message(&format!(
+                    "Broken link: {} -> {} ({})",
+                    entry.name, dest, e
+                ));
+                return;
+            }
+        };
+        let Some(parent) = target.parent().map(Path::to_path_buf)
+        else
+        {
+            // The link points at the filesystem root
+            self.enter_dir(&target);
+            return;
+        };
+        let name = target.file_name().map(|s| s.to_string_lossy().to_string());
+        self.enter_dir_selecting(&parent, name);
+    }
+
+    /// Change to the directory named by `input`: absolute, `~`-prefixed, or
+    /// relative to the cwd. Anything else is reported and the cwd is kept.
+    pub(crate) fn goto_path(
+        &mut self,
+        input: &str,
+    )
+    {
+        let input = input.trim();
+        if input.is_empty()
+        {
+            return;
+        }
+        let path = crate::util::expand_tilde(input);
+        let path = if path.is_absolute() { path } else { self.cwd.join(path) };
+        match std::fs::canonicalize(&path)
+        {
+            Ok(dir) if dir.is_dir() => self.enter_dir(&dir),
+            Ok(_) =>
+            {
+                self.add_message(&format!("Go to: not a directory: {}", input))
+            }
+            Err(e) => self.add_message(&format!("Go to: {}: {}", input, e)),
+        }
+    }
+
+    /// Select the entry at 1-based line `input` (as shown by
+    /// `ui.line_numbers`), clamped to the last entry.
+    pub(crate) fn goto_line(
+        &mut self,
+        input: &str,
+    )
+    {
+        let input = input.trim();
+        if input.is_empty() || self.current_entries.is_empty()
+        {
+            return;
+        }
+        match input.parse::<usize>()
+        {
+            Ok(n) if n > 0 =>
+            {
+                let idx = (n - 1).min(self.current_entries.len() - 1);
+                self.select_index(idx);
+            }
+            _ => self
+                .add_message(&format!("Go to line: not a number: {}", input)),
         }
     }
 
+    /// Refresh the listing and select `input` (absolute, `~`-prefixed or
+    /// relative to the cwd), changing to its directory first when it lives
+    /// elsewhere.
+    pub(crate) fn reveal_path(
+        &mut self,
+        input: &str,
+    )
+    {
+        let input = input.trim();
+        if input.is_empty()
+        {
+            return;
+        }
+        let path = crate::util::expand_tilde(input);
+        let path = if path.is_absolute() { path } else { self.cwd.join(path) };
+        let (Some(dir), Some(name)) = (path.parent(), path.file_name())
+        else
+        {
+            self.add_message(&format!("Reveal: not a file path: {}", input));
+            return;
+        };
+        let name = name.to_string_lossy().to_string();
+        let dir = match std::fs::canonicalize(dir)
+        {
+            Ok(d) if d.is_dir() => d,
+            _ =>
+            {
+                self.add_message(&format!(
+                    "Reveal: no such directory: {}",
+                    input
+                ));
+                return;
+            }
+        };
+        // Stay on the cwd as spelled when it is the same directory
+        let dir = if std::fs::canonicalize(&self.cwd).is_ok_and(|c| c == dir)
+        {
+            self.cwd.clone()
+        }
+        else
+        {
+            dir
+        };
+        self.enter_dir_selecting(&dir, Some(name.clone()));
+        if self.selected_entry().is_none_or(|e| e.name != name)
+        {
+            self.add_message(&format!(
+                "Reveal: {} is not in the listing",
+                name
+            ));
+        }
+        self.force_full_redraw = true;
+    }
+
+    pub fn set_cwd(
+        &mut self,
+        path: &Path,
+    )
+    {
+        self.enter_dir(path);
+    }
+
     pub fn current_has_entries(&self) -> bool
     {
         !self.current_entries.is_empty()
@@ -5,6 +5,7 @@ use std::fs;
 use crate::app::{
     App,
     Overlay,
+    TemplatePickerState,
     ThemePickerEntry,
     ThemePickerState,
 };
@@ -137,6 +138,53 @@ impl App
         self.force_full_redraw = true;
     }
 
+    /// List the files in `<config>/templates` for `file:new_from_template`.
+    pub(crate) fn open_template_picker(&mut self)
+    {
+        let Some(root) = self.theme_root_dir()
+        else
+        {
+            self.add_message("Templates: unable to determine config directory");
+            return;
+        };
+        let dir = root.join("templates");
+        let mut entries: Vec<std::path::PathBuf> = match fs::read_dir(&dir)
+        {
+            Ok(rd) => rd
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.is_file())
+                .collect(),
+            Err(_) =>
+            {
+                self.add_message(&format!(
+                    "Templates: no templates directory at {}",
+                    dir.display()
+                ));
+                return;
+            }
+        };
+        if entries.is_empty()
+        {
+            self.add_message(&format!(
+                "Templates: no files found in {}",
+                dir.display()
+            ));
+            return;
+        }
+        entries.sort_by_key(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().to_lowercase())
+                .unwrap_or_default()
+        });
+        self.keys.pending.clear();
+        self.keys.last_at = None;
+        self.overlay = Overlay::TemplatePicker(Box::new(TemplatePickerState {
+            entries,
+            selected: 0,
+        }));
+        self.force_full_redraw = true;
+    }
+
     pub(crate) fn open_add_entry_prompt(&mut self)
     {
         crate::core::overlays::open_add_entry_prompt(self)
@@ -147,22 +195,114 @@ impl App
         crate::core::overlays::open_rename_entry_prompt(self)
     }
 
+    /// Show a depth-limited tree of the selected directory (or the cwd when
+    /// a file is selected) in the Output overlay.
+    pub(crate) fn show_tree(&mut self)
+    {
+        const TREE_MAX_DEPTH: usize = 3;
+        const TREE_MAX_LINES: usize = 2000;
+        let root = match self.selected_entry()
+        {
+            Some(e) if e.is_dir => e.path.clone(),
+            _ => self.cwd.clone(),
+        };
+        let text = crate::core::tree::tree_text(
+            &root,
+            TREE_MAX_DEPTH,
+            self.config.ui.show_hidden,
+            TREE_MAX_LINES,
+        );
+        self.display_output(&format!("Tree: {}", root.display()), &text);
+    }
+
+    /// Show the tail of the trace log in the Output overlay.
+    pub(crate) fn open_trace_log(&mut self)
+    {
+        const TRACE_TAIL_LINES: usize = 500;
+        if !crate::trace::enabled()
+        {
+            self.add_message(
+                "Tracing is disabled; restart with LSV_TRACE=1 (or --trace)",
+            );
+            return;
+        }
+        let Some(path) = crate::trace::file_path()
+        else
+        {
+            self.add_message("Trace: unable to determine log path");
+            return;
+        };
+        match std::fs::read_to_string(&path)
+        {
+            Ok(text) =>
+            {
+                let lines: Vec<&str> = text.lines().collect();
+                let start = lines.len().saturating_sub(TRACE_TAIL_LINES);
+                let tail = lines[start..].join("\n");
+                self.display_output(
+                    &format!("Trace: {}", path.display()),
+                    &tail,
+                );
+            }
+            Err(e) =>
+            {
+                self.add_message(&format!(
+                    "Trace: cannot read {} ({})",
+                    path.display(),
+                    e
+                ));
+            }
+        }
+    }
+
     pub(crate) fn request_delete_selected(&mut self)
     {
         crate::core::overlays::request_delete_selected(self)
     }
 
+    /// Delete each path in turn. With `ui.bulk_report` the per-item
+    /// outcomes are listed in the Output overlay.
+    pub(crate) fn perform_delete_paths(
+        &mut self,
+        paths: &[std::path::PathBuf],
+    )
+    {
+        let mut report: Vec<String> = Vec::new();
+        let mut errs = 0usize;
+        for p in paths
+        {
+            match self.perform_delete_path(p)
+            {
+                Ok(()) => report.push(format!("Deleted: {}", p.display())),
+                Err(e) =>
+                {
+                    errs += 1;
+                    report.push(format!("Error: {}: {}", p.display(), e));
+                }
+            }
+        }
+        if self.config.ui.bulk_report
+        {
+            report.push(format!(
+                "Delete: ok={} errors={}",
+                paths.len() - errs,
+                errs
+            ));
+            self.display_output("Delete results", &report.join("\n"));
+        }
+    }
+
     pub(crate) fn perform_delete_path(
         &mut self,
         path: &std::path::Path,
-    )
+    ) -> std::io::Result<()>
     {
         crate::trace::log(format!(
             "[delete] perform path='{}'",
             path.display()
         ));
         let res = crate::core::fs_ops::remove_path_all(path);
-        match res
+        match &res
         {
             Ok(_) =>
             {
@@ -178,5 +318,6 @@ impl App
         }
         self.refresh_lists();
         self.refresh_preview();
+        res
     }
 }
@@ -4,7 +4,172 @@ use crate::app::App;
 
 impl App
 {
+    /// Refresh the preview for the current selection. With
+    /// `ui.preview_debounce_ms > 0` the refresh is deferred until the
+    /// selection has settled; see [`App::flush_pending_preview`].
     pub(crate) fn refresh_preview(&mut self)
+    {
+        // Keeping the last file needs no work, so it skips the debounce
+        if self.config.ui.preview_debounce_ms > 0
+            && self.preview_file_target().is_none_or(|(_, kept)| !kept)
+        {
+            self.preview_pending_since = Some(std::time::Instant::now());
+            self.preview.static_lines.clear();
+            self.preview.cache_key = None;
+            self.preview.cache_lines = None;
+            return;
+        }
+        self.refresh_preview_now();
+    }
+
+    /// Drop the cached previewer output and re-run the preview for the
+    /// current selection now, skipping any debounce.
+    pub(crate) fn force_refresh_preview(&mut self)
+    {
+        self.preview.cache_key = None;
+        self.preview.cache_lines = None;
+        self.preview_pending_since = None;
+        self.refresh_preview_now();
+        self.force_full_redraw = true;
+    }
+
+    /// Run a deferred preview refresh once the debounce interval has elapsed
+    /// since the last selection change. Returns true if the preview was
+    /// refreshed.
+    pub fn flush_pending_preview(&mut self) -> bool
+    {
+        let debounce = std::time::Duration::from_millis(
+            self.config.ui.preview_debounce_ms,
+        );
+        match self.preview_pending_since
+        {
+            Some(at) if at.elapsed() >= debounce =>
+            {
+                self.preview_pending_since = None;
+                self.refresh_preview_now();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// True while background work (async preview, debounced preview,
+    /// directory sizes) is in progress; drives the header spinner.
+    pub(crate) fn is_busy(&self) -> bool
+    {
+        self.running_preview.is_some()
+            || self.preview_pending_since.is_some()
+            || self.dir_sizes.is_busy()
+            || self.listing_stream.is_some()
+            || self.jobs.is_busy()
+    }
+
+    /// Time left before a pending preview refresh is due, if any.
+    pub(crate) fn preview_debounce_remaining(
+        &self
+    ) -> Option<std::time::Duration>
+    {
+        let at = self.preview_pending_since?;
+        let debounce = std::time::Duration::from_millis(
+            self.config.ui.preview_debounce_ms,
+        );
+        Some(debounce.saturating_sub(at.elapsed()))
+    }
+
+    /// True with `ui.preview_follows = "parent"`.
+    pub(crate) fn preview_follows_parent(&self) -> bool
+    {
+        self.config
+            .ui
+            .preview_follows
+            .as_deref()
+            .is_some_and(|s| s.trim().eq_ignore_ascii_case("parent"))
+    }
+
+    /// True with `ui.dir_preview = "tree"`.
+    pub(crate) fn dir_preview_tree(&self) -> bool
+    {
+        self.config
+            .ui
+            .dir_preview
+            .as_deref()
+            .is_some_and(|s| s.trim().eq_ignore_ascii_case("tree"))
+    }
+
+    /// Index of the parent pane's selection: the entry the parent cursor
+    /// rests on while still in the directory it was moved in, else the
+    /// current directory's entry.
+    pub(crate) fn parent_selected_index(&self) -> Option<usize>
+    {
+        let cursor = self
+            .parent_cursor
+            .as_ref()
+            .filter(|(cwd, _)| *cwd == self.cwd)
+            .and_then(|(_, path)| {
+                self.parent_entries.iter().position(|e| &e.path == path)
+            });
+        cursor.or_else(|| {
+            self.parent_entries.iter().position(|e| e.path == self.cwd)
+        })
+    }
+
+    /// Move the parent pane's cursor by `delta` entries, clamped to the list
+    /// (`nav:parent_up`/`nav:parent_down`).
+    pub(crate) fn move_parent_cursor(
+        &mut self,
+        delta: isize,
+    )
+    {
+        let Some(cur) = self.parent_selected_index()
+        else
+        {
+            return;
+        };
+        let last = self.parent_entries.len().saturating_sub(1) as isize;
+        let next = (cur as isize + delta).clamp(0, last) as usize;
+        if next == cur
+        {
+            return;
+        }
+        self.parent_cursor =
+            Some((self.cwd.clone(), self.parent_entries[next].path.clone()));
+        self.refresh_preview();
+    }
+
+    /// The entry the preview describes: the current pane's selection, or the
+    /// parent pane's with `ui.preview_follows = "parent"`.
+    pub(crate) fn preview_entry(&self) -> Option<&crate::app::DirEntryInfo>
+    {
+        if self.preview_follows_parent()
+        {
+            return self
+                .parent_selected_index()
+                .and_then(|i| self.parent_entries.get(i));
+        }
+        self.selected_entry()
+    }
+
+    /// The file whose preview should be shown, and whether it is the kept
+    /// last file because a directory is selected under
+    /// `ui.preview_keep_file`. `None` when a directory listing (or nothing)
+    /// should be shown.
+    pub(crate) fn preview_file_target(
+        &self
+    ) -> Option<(std::path::PathBuf, bool)>
+    {
+        let sel = self.preview_entry()?;
+        if !sel.is_dir
+        {
+            return Some((sel.path.clone(), false));
+        }
+        if self.config.ui.preview_keep_file
+        {
+            return self.preview.last_file.clone().map(|p| (p, true));
+        }
+        None
+    }
+
+    fn refresh_preview_now(&mut self)
     {
         if self.running_preview.is_some()
         {
@@ -13,7 +178,7 @@ impl App
         }
         // Avoid borrowing self while mutating by cloning the needed fields
         // first
-        let (is_dir, path) = match self.selected_entry()
+        let (is_dir, path) = match self.preview_entry()
         {
             Some(e) => (e.is_dir, e.path.clone()),
             None =>
@@ -28,6 +193,15 @@ impl App
 
         const PREVIEW_LINES_LIMIT: usize = 200;
         let preview_limit = PREVIEW_LINES_LIMIT;
+        if is_dir && self.preview_file_target().is_some()
+        {
+            // ui.preview_keep_file: leave the last file's preview in place
+            return;
+        }
+        if !is_dir
+        {
+            self.preview.last_file = Some(path.clone());
+        }
         if is_dir
         {
             match self.read_dir_sorted(&path)
@@ -53,7 +227,22 @@ impl App
         else
         {
             // Detect binary early to avoid rendering junk or huge wrapped lines
-            if crate::util::is_binary(&path)
+            let binary = crate::util::is_binary(&path);
+            if binary
+                && let Some(listing) =
+                    crate::util::list_archive(&path, preview_limit)
+            {
+                self.preview.static_lines = listing
+                    .map(|v| {
+                        v.into_iter()
+                            .map(|s| crate::util::sanitize_line(&s))
+                            .collect()
+                    })
+                    .unwrap_or_else(|e| {
+                        vec![format!("<error reading archive: {}>", e)]
+                    });
+            }
+            else if binary
             {
                 let size =
                     std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
@@ -67,15 +256,35 @@ impl App
             {
                 // Cap bytes and lines to avoid runaway previews for huge files
                 const HEAD_BYTES_LIMIT: usize = 128 * 1024; // 128 KiB cap
+                let highlight = self.config.ui.syntax_highlight;
+                let squeeze = self.config.ui.preview_squeeze_blank;
                 self.preview.static_lines = crate::util::read_file_head_safe(
                     &path,
                     HEAD_BYTES_LIMIT,
                     preview_limit,
                 )
                 .map(|v| {
-                    v.into_iter()
+                    let lines: Vec<String> = v
+                        .into_iter()
                         .map(|s| crate::util::sanitize_line(&s))
-                        .collect()
+                        .collect();
+                    let lines = if squeeze
+                    {
+                        crate::util::squeeze_blank_lines(lines)
+                    }
+                    else
+                    {
+                        lines
+                    };
+                    if highlight
+                    {
+                        crate::util::highlight_lines(&path, &lines)
+                            .unwrap_or(lines)
+                    }
+                    else
+                    {
+                        lines
+                    }
                 })
                 .unwrap_or_else(|e| {
                     vec![format!("<error reading file: {}>", e)]
@@ -215,4 +424,43 @@ impl App
             }
         }
     }
+
+    /// Hide or show the preview pane. When hiding, the current split is
+    /// remembered so showing it again restores the exact layout.
+    pub(crate) fn toggle_preview_pane(&mut self)
+    {
+        let current = self
+            .config
+            .ui
+            .panes
+            .clone()
+            .unwrap_or_else(crate::config::defaults::default_panes);
+        if current.preview > 0
+        {
+            let mut hidden = current.clone();
+            hidden.preview = 0;
+            self.saved_panes = Some(current);
+            self.config.ui.panes = Some(hidden);
+        }
+        else
+        {
+            let restored = self
+                .saved_panes
+                .take()
+                .unwrap_or_else(crate::config::defaults::default_panes);
+            self.config.ui.panes = Some(restored);
+        }
+        self.preview.cache_key = None;
+        self.preview.cache_lines = None;
+        self.force_full_redraw = true;
+    }
+
+    /// Switch the preview between wrapped and clipped long lines. The state
+    /// lives on App so it survives navigation; `ui.preview_wrap` sets the
+    /// initial value.
+    pub(crate) fn toggle_preview_wrap(&mut self)
+    {
+        self.preview_wrap = !self.preview_wrap;
+        self.force_full_redraw = true;
+    }
 }
@@ -6,6 +6,9 @@ use crate::app::{
     ClipboardOp,
 };
 
+/// Entries walked per directory when a size is computed on the UI thread.
+const SIZE_MAX_ENTRIES: usize = 200_000;
+
 impl App
 {
     pub(crate) fn toggle_select_current(&mut self)
@@ -23,6 +26,74 @@ impl App
         }
     }
 
+    /// Select every entry in the current listing.
+    pub(crate) fn select_all_visible(&mut self)
+    {
+        for e in &self.current_entries
+        {
+            self.selected.insert(e.path.clone());
+        }
+        self.force_full_redraw = true;
+    }
+
+    /// Toggle selection of every entry in the current listing. Selected
+    /// paths outside the listing are left as they are.
+    pub(crate) fn invert_selection(&mut self)
+    {
+        for e in &self.current_entries
+        {
+            if !self.selected.remove(&e.path)
+            {
+                self.selected.insert(e.path.clone());
+            }
+        }
+        self.force_full_redraw = true;
+    }
+
+    /// Start visual mode at the current entry, or mark the range and leave
+    /// it when already active.
+    pub(crate) fn toggle_visual_select(&mut self)
+    {
+        if self.visual_anchor.is_some()
+        {
+            self.commit_visual_select();
+        }
+        else
+        {
+            self.visual_anchor = self.list_state.selected();
+        }
+        self.force_full_redraw = true;
+    }
+
+    /// Indices between the visual anchor and the cursor, inclusive.
+    pub fn visual_range(&self) -> Option<std::ops::RangeInclusive<usize>>
+    {
+        let anchor = self.visual_anchor?;
+        let cur = self.list_state.selected()?;
+        let last = self.current_entries.len().checked_sub(1)?;
+        Some(anchor.min(cur).min(last)..=anchor.max(cur).min(last))
+    }
+
+    /// Add every entry in the visual range to the selection.
+    pub(crate) fn commit_visual_select(&mut self)
+    {
+        if let Some(range) = self.visual_range()
+        {
+            for e in &self.current_entries[range]
+            {
+                self.selected.insert(e.path.clone());
+            }
+        }
+        self.visual_anchor = None;
+        self.force_full_redraw = true;
+    }
+
+    pub(crate) fn cancel_visual_select(&mut self)
+    {
+        self.visual_anchor = None;
+        self.force_full_redraw = true;
+    }
+
     pub(crate) fn clear_all_selected(&mut self)
     {
         if !self.selected.is_empty()
@@ -59,6 +130,435 @@ impl App
         self.force_full_redraw = true;
     }
 
+    /// Copy the paths of the selected entries (or the entry under the
+    /// cursor) to the system clipboard, one per line. With `relative` they
+    /// are relative to the cwd, falling back to `~/...` and then absolute.
+    pub(crate) fn yank_paths(
+        &mut self,
+        relative: bool,
+    )
+    {
+        let mut paths: Vec<std::path::PathBuf> =
+            self.selected.iter().cloned().collect();
+        paths.sort();
+        if paths.is_empty()
+            && let Some(e) = self.selected_entry()
+        {
+            paths.push(e.path.clone());
+        }
+        if paths.is_empty()
+        {
+            self.add_message("Yank: nothing to copy");
+            return;
+        }
+        let text = paths
+            .iter()
+            .map(|p| {
+                if relative
+                {
+                    crate::util::relative_path(p, &self.cwd)
+                }
+                else
+                {
+                    p.to_string_lossy().to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.yank_text(&text, &format!("{} path(s)", paths.len()));
+    }
+
+    /// Copy the file names (no directory) of the selected items, or of the
+    /// current entry, to the system clipboard, one per line.
+    pub(crate) fn yank_names(&mut self)
+    {
+        let mut paths: Vec<std::path::PathBuf> =
+            self.selected.iter().cloned().collect();
+        paths.sort();
+        if paths.is_empty()
+            && let Some(e) = self.selected_entry()
+        {
+            paths.push(e.path.clone());
+        }
+        let names: Vec<String> = paths
+            .iter()
+            .filter_map(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .collect();
+        if names.is_empty()
+        {
+            self.add_message("Yank: nothing to copy");
+            return;
+        }
+        let text = names.join("\n");
+        let what = if names.len() == 1
+        {
+            text.clone()
+        }
+        else
+        {
+            format!("{} name(s)", names.len())
+        };
+        self.yank_text(&text, &what);
+    }
+
+    /// Copy the selected paths (or the current entry) beside themselves
+    /// under the next free ` (n)` name, then select the last copy made in
+    /// the cwd.
+    pub(crate) fn duplicate_selected(&mut self)
+    {
+        let mut paths: Vec<std::path::PathBuf> =
+            self.selected.iter().cloned().collect();
+        paths.sort();
+        if paths.is_empty()
+            && let Some(e) = self.selected_entry()
+        {
+            paths.push(e.path.clone());
+        }
+        if paths.is_empty()
+        {
+            self.add_message("Duplicate: nothing selected");
+            return;
+        }
+        let mut last_here = None;
+        for src in paths
+        {
+            let dest = crate::core::fs_ops::next_free_copy_name(&src);
+            match crate::core::fs_ops::copy_path_recursive(&src, &dest)
+            {
+                Ok(()) =>
+                {
+                    let name = dest
+                        .file_name()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    self.add_message(&format!("Duplicated as {}", name));
+                    if dest.parent() == Some(self.cwd.as_path())
+                    {
+                        last_here = Some(name);
+                    }
+                }
+                Err(e) =>
+                {
+                    self.add_message(&format!(
+                        "Duplicate error: {}: {}",
+                        src.display(),
+                        e
+                    ));
+                }
+            }
+        }
+        self.refresh_lists();
+        if let Some(name) = last_here
+        {
+            crate::core::selection::reselect_by_name(self, &name);
+        }
+        self.refresh_preview();
+        self.force_full_redraw = true;
+    }
+
+    /// Bump the access and modification times of the selected items (or the
+    /// current entry) to now.
+    pub(crate) fn touch_selected(&mut self)
+    {
+        let mut paths: Vec<std::path::PathBuf> =
+            self.selected.iter().cloned().collect();
+        paths.sort();
+        if paths.is_empty()
+            && let Some(e) = self.selected_entry()
+        {
+            paths.push(e.path.clone());
+        }
+        if paths.is_empty()
+        {
+            self.add_message("Touch: nothing selected");
+            return;
+        }
+        let mut touched = 0usize;
+        for path in paths
+        {
+            match crate::core::fs_ops::touch_path(&path)
+            {
+                Ok(_) => touched += 1,
+                Err(e) =>
+                {
+                    self.add_message(&format!(
+                        "Touch error: {}: {}",
+                        path.display(),
+                        e
+                    ));
+                }
+            }
+        }
+        if touched > 0
+        {
+            self.add_message(&format!("Touched {} item(s)", touched));
+        }
+        let current_name = self.selected_entry().map(|e| e.name.clone());
+        self.refresh_lists();
+        if let Some(name) = current_name
+        {
+            crate::core::selection::reselect_by_name(self, &name);
+        }
+        self.refresh_preview();
+    }
+
+    /// Touch `name` (relative to the cwd, or absolute), creating an empty
+    /// file when it does not exist, and select it when it is listed here.
+    pub(crate) fn touch_named(
+        &mut self,
+        name: &str,
+    )
+    {
+        let path = self.cwd.join(crate::util::expand_tilde(name));
+        match crate::core::fs_ops::touch_path(&path)
+        {
+            Ok(created) =>
+            {
+                let verb = if created { "Created" } else { "Touched" };
+                self.add_message(&format!("{} {}", verb, path.display()));
+                self.refresh_lists();
+                if path.parent() == Some(self.cwd.as_path())
+                    && let Some(n) = path.file_name()
+                {
+                    crate::core::selection::reselect_by_name(
+                        self,
+                        &n.to_string_lossy(),
+                    );
+                }
+                self.refresh_preview();
+            }
+            Err(e) =>
+            {
+                self.add_message(&format!(
+                    "Touch error: {}: {}",
+                    path.display(),
+                    e
+                ));
+            }
+        }
+    }
+
+    /// Copy `template` to `name` in the cwd and select the new file. An
+    /// existing entry is never overwritten.
+    pub(crate) fn create_from_template(
+        &mut self,
+        template: &std::path::Path,
+        name: &str,
+    )
+    {
+        let dest = self.cwd.join(name);
+        if dest.symlink_metadata().is_ok()
+        {
+            self.add_message(&format!("Exists: {}", dest.display()));
+            return;
+        }
+        match crate::core::fs_ops::copy_path_recursive(template, &dest)
+        {
+            Ok(()) =>
+            {
+                self.add_message(&format!("Created {}", dest.display()));
+                self.refresh_lists();
+                if dest.parent() == Some(self.cwd.as_path())
+                    && let Some(n) = dest.file_name()
+                {
+                    crate::core::selection::reselect_by_name(
+                        self,
+                        &n.to_string_lossy(),
+                    );
+                }
+                self.refresh_preview();
+            }
+            Err(e) =>
+            {
+                self.add_message(&format!(
+                    "Template error: {}: {}",
+                    dest.display(),
+                    e
+                ));
+            }
+        }
+    }
+
+    /// Copy the selected text file's contents to the system clipboard.
+    /// Directories, binary files and files over 64 KiB (more than OSC 52
+    /// terminals reliably accept) are refused with a message.
+    pub(crate) fn yank_contents(&mut self)
+    {
+        const YANK_CONTENTS_MAX_BYTES: u64 = 64 * 1024;
+        let Some(e) = self.selected_entry().cloned()
+        else
+        {
+            self.add_message("Yank: nothing selected");
+            return;
+        };
+        if e.is_dir
+        {
+            self.add_message(&format!("Yank: {} is a directory", e.name));
+            return;
+        }
+        let len = std::fs::metadata(&e.path).map(|m| m.len()).unwrap_or(0);
+        if len > YANK_CONTENTS_MAX_BYTES
+        {
+            self.add_message(&format!(
+                "Yank: {} is too large ({} bytes, limit {})",
+                e.name, len, YANK_CONTENTS_MAX_BYTES
+            ));
+            return;
+        }
+        if crate::util::is_binary(&e.path)
+        {
+            self.add_message(&format!("Yank: {} is a binary file", e.name));
+            return;
+        }
+        match std::fs::read_to_string(&e.path)
+        {
+            Ok(text) =>
+            {
+                self.yank_text(&text, &format!("contents of {}", e.name))
+            }
+            Err(err) => self.add_message(&format!("Yank: {}: {}", e.name, err)),
+        }
+    }
+
+    /// Copy the cwd's last path component to the system clipboard. The root
+    /// directory has no name, so its full path (`/`, `C:\`) is copied.
+    pub(crate) fn yank_dirname(&mut self)
+    {
+        let name = self
+            .cwd
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.cwd.to_string_lossy().to_string());
+        self.yank_text(&name, &format!("directory name {}", name));
+    }
+
+    /// Copy the selected entry's size in bytes to the system clipboard.
+    /// Directory sizes come from the `ui.dir_sizes` cache when it has them,
+    /// else from a walk bounded like [`Self::report_selection_size`]; a
+    /// cut-short walk is reported as a lower bound.
+    pub(crate) fn yank_size(&mut self)
+    {
+        let Some(e) = self.selected_entry()
+        else
+        {
+            self.add_message("Yank: nothing selected");
+            return;
+        };
+        let (size, cut) = if e.is_dir
+        {
+            match self.dir_sizes.get(&e.path, e.mtime)
+            {
+                Some(size) => (size, false),
+                None => crate::core::dir_sizes::dir_size_bounded(
+                    &e.path,
+                    SIZE_MAX_ENTRIES,
+                ),
+            }
+        }
+        else
+        {
+            let len =
+                std::fs::metadata(&e.path).map(|m| m.len()).unwrap_or(e.size);
+            (len, false)
+        };
+        let what = if cut
+        {
+            format!("size of at least {} bytes (walk cut short)", size)
+        }
+        else
+        {
+            format!("size {} bytes", size)
+        };
+        self.yank_text(&size.to_string(), &what);
+    }
+
+    /// Show the size of each marked entry (or the current one) and their
+    /// total in the Output overlay. Directories are walked recursively, up to
+    /// a bound per directory; sizes from a cut-short walk are shown as `≥`.
+    pub(crate) fn report_selection_size(&mut self)
+    {
+        let mut paths: Vec<std::path::PathBuf> =
+            self.selected.iter().cloned().collect();
+        paths.sort();
+        if paths.is_empty()
+            && let Some(e) = self.selected_entry()
+        {
+            paths.push(e.path.clone());
+        }
+        if paths.is_empty()
+        {
+            self.add_message("Size: nothing selected");
+            return;
+        }
+        let fmt = |bytes: u64| match self.display_mode
+        {
+            crate::app::DisplayMode::Friendly =>
+            {
+                crate::ui::format::human_size(bytes)
+            }
+            crate::app::DisplayMode::Absolute => format!("{} B", bytes),
+        };
+        let mut total = 0u64;
+        let mut partial = false;
+        let mut lines = Vec::with_capacity(paths.len() + 2);
+        for p in &paths
+        {
+            let (size, cut) = match std::fs::symlink_metadata(p)
+            {
+                Ok(m) if m.is_dir() =>
+                {
+                    crate::core::dir_sizes::dir_size_bounded(
+                        p,
+                        SIZE_MAX_ENTRIES,
+                    )
+                }
+                Ok(m) => (m.len(), false),
+                Err(_) => (0, false),
+            };
+            total = total.saturating_add(size);
+            partial |= cut;
+            let name = crate::util::relative_path(p, &self.cwd);
+            let slash = if p.is_dir() { "/" } else { "" };
+            lines.push(format!(
+                "{}{:>10}  {}{}",
+                if cut { "≥" } else { " " },
+                fmt(size),
+                name,
+                slash
+            ));
+        }
+        lines.push(String::new());
+        lines.push(format!(
+            "Total: {}{} in {} item(s)",
+            if partial { "≥ " } else { "" },
+            fmt(total),
+            paths.len()
+        ));
+        self.display_output("Selection size", &lines.join("\n"));
+    }
+
+    /// Put `text` on the system clipboard and report it as `what`.
+    pub(crate) fn yank_text(
+        &mut self,
+        text: &str,
+        what: &str,
+    )
+    {
+        match crate::core::os_clipboard::copy_text(
+            text,
+            self.config.ui.clipboard_tool,
+        )
+        {
+            Ok(()) =>
+            {
+                self.last_yank = Some(text.to_string());
+                self.add_message(&format!("Copied {}", what));
+            }
+            Err(e) => self.add_message(&format!("Copy failed: {}", e)),
+        }
+    }
+
     pub(crate) fn clear_clipboard(&mut self)
     {
         self.clipboard = None;
@@ -68,40 +568,156 @@ impl App
 
     pub(crate) fn paste_clipboard(&mut self)
     {
-        let Some(cb) = self.clipboard.clone()
+        let Some(cb) = self.clipboard.take()
         else
         {
             self.add_message("Paste: clipboard empty");
             return;
         };
+        self.paste_items(&cb);
+    }
+
+    /// Copy or move the selected items (or the current entry) into the
+    /// directory named by `input` (absolute, `~`-prefixed or relative to the
+    /// cwd) in one step, without going through the clipboard. Existing
+    /// targets are skipped, as with paste.
+    pub(crate) fn transfer_selected_to(
+        &mut self,
+        input: &str,
+        op: ClipboardOp,
+    )
+    {
+        let (verb, label) = match op
+        {
+            ClipboardOp::Copy => ("Copy", "Copy to"),
+            ClipboardOp::Move => ("Move", "Move to"),
+        };
+        let input = input.trim();
+        if input.is_empty()
+        {
+            return;
+        }
+        let path = crate::util::expand_tilde(input);
+        let path = if path.is_absolute() { path } else { self.cwd.join(path) };
+        let dest = match std::fs::canonicalize(&path)
+        {
+            Ok(dir) if dir.is_dir() => dir,
+            Ok(_) =>
+            {
+                self.add_message(&format!(
+                    "{}: not a directory: {}",
+                    label, input
+                ));
+                return;
+            }
+            Err(e) =>
+            {
+                self.add_message(&format!("{}: {}: {}", label, input, e));
+                return;
+            }
+        };
+        let mut items: Vec<std::path::PathBuf> =
+            self.selected.iter().cloned().collect();
+        items.sort();
+        if items.is_empty()
+            && let Some(e) = self.selected_entry()
+        {
+            items.push(e.path.clone());
+        }
+        if items.is_empty()
+        {
+            self.add_message(&format!("{}: nothing selected", label));
+            return;
+        }
+        let cb = Clipboard { op, items };
+        self.transfer_items(&cb, &dest, verb);
+    }
+
+    /// Store the selected paths in register `reg`, independent of the main
+    /// clipboard and of other registers.
+    pub(crate) fn copy_to_register(
+        &mut self,
+        reg: char,
+    )
+    {
+        let mut items: Vec<std::path::PathBuf> =
+            self.selected.iter().cloned().collect();
+        if items.is_empty()
+        {
+            self.add_message("Register: no items selected");
+            return;
+        }
+        items.sort();
+        let n = items.len();
+        self.registers.insert(reg, Clipboard { op: ClipboardOp::Copy, items });
+        self.add_message(&format!(
+            "Copied {} item(s) to register '{}'",
+            n, reg
+        ));
+    }
+
+    /// Paste (copy) the contents of register `reg` into the cwd. The register
+    /// is kept so it can be pasted again.
+    pub(crate) fn paste_register(
+        &mut self,
+        reg: char,
+    )
+    {
+        let Some(cb) = self.registers.get(&reg).cloned()
+        else
+        {
+            self.add_message(&format!("Register '{}' is empty", reg));
+            return;
+        };
+        self.paste_items(&cb);
+    }
+
+    fn paste_items(
+        &mut self,
+        cb: &Clipboard,
+    )
+    {
         let dest_dir = self.cwd.clone();
+        self.transfer_items(cb, &dest_dir, "Paste");
+    }
+
+    /// Copy or move `cb`'s items into `dest_dir`; `verb` labels the summary
+    /// message and the `ui.bulk_report` output.
+    fn transfer_items(
+        &mut self,
+        cb: &Clipboard,
+        dest_dir: &std::path::Path,
+        verb: &str,
+    )
+    {
         let mut ok = 0usize;
         let mut skipped = 0usize;
         let mut errs = 0usize;
+        let mut report: Vec<String> = Vec::new();
         for src in cb.items.iter()
         {
             if matches!(cb.op, ClipboardOp::Move) && dest_dir.starts_with(src)
             {
-                self.add_message(&format!(
-                    "Skip (move into subdir): {}",
-                    src.display()
-                ));
+                let line =
+                    format!("Skip (move into subdir): {}", src.display());
+                self.add_message(&line);
+                report.push(line);
                 skipped += 1;
                 continue;
             }
             let Some(name) = src.file_name()
             else
             {
+                report.push(format!("Skip (no file name): {}", src.display()));
                 skipped += 1;
                 continue;
             };
             let dest_path = dest_dir.join(name);
             if dest_path.exists()
             {
-                self.add_message(&format!(
-                    "Skip (exists): {}",
-                    dest_path.display()
-                ));
+                let line = format!("Skip (exists): {}", dest_path.display());
+                self.add_message(&line);
+                report.push(line);
                 skipped += 1;
                 continue;
             }
@@ -120,16 +736,26 @@ impl App
             };
             match res
             {
-                Ok(()) => ok += 1,
+                Ok(()) =>
+                {
+                    ok += 1;
+                    report.push(format!(
+                        "Ok: {} -> {}",
+                        src.display(),
+                        dest_path.display()
+                    ));
+                }
                 Err(e) =>
                 {
                     errs += 1;
-                    self.add_message(&format!(
+                    let line = format!(
                         "Error: {} -> {}: {}",
                         src.display(),
                         dest_path.display(),
                         e
-                    ));
+                    );
+                    self.add_message(&line);
+                    report.push(line);
                 }
             }
         }
@@ -140,13 +766,19 @@ impl App
                 self.selected.remove(p);
             }
         }
-        self.clipboard = None;
         self.refresh_lists();
         self.refresh_preview();
-        self.add_message(&format!(
-            "Paste: ok={} skipped={} errors={}",
-            ok, skipped, errs
-        ));
+        let summary =
+            format!("{}: ok={} skipped={} errors={}", verb, ok, skipped, errs);
+        self.add_message(&summary);
+        if self.config.ui.bulk_report
+        {
+            report.push(summary);
+            self.display_output(
+                &format!("{} results", verb),
+                &report.join("\n"),
+            );
+        }
         self.force_full_redraw = true;
     }
 }
@@ -11,12 +11,27 @@ use ratatui::widgets::ListState;
 /// flags, and configuration.
 pub struct DirEntryInfo
 {
-    pub(crate) name:   String,
-    pub(crate) path:   PathBuf,
-    pub(crate) is_dir: bool,
-    pub(crate) size:   u64,
-    pub(crate) mtime:  Option<SystemTime>,
-    pub(crate) ctime:  Option<SystemTime>,
+    pub(crate) name:        String,
+    pub(crate) path:        PathBuf,
+    pub(crate) is_dir:      bool,
+    pub(crate) size:        u64,
+    pub(crate) mtime:       Option<SystemTime>,
+    pub(crate) ctime:       Option<SystemTime>,
+    pub(crate) atime:       Option<SystemTime>,
+    // Type or metadata could not be read (permission denied, broken link)
+    pub(crate) unreadable:  bool,
+    pub(crate) is_symlink:  bool,
+    // Raw `read_link` target, shown as `name -> target`
+    pub(crate) link_target: Option<PathBuf>,
+}
+
+impl DirEntryInfo
+{
+    /// Size in bytes as listed (0 when metadata was not read).
+    pub fn size(&self) -> u64
+    {
+        self.size
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -36,6 +51,14 @@ pub struct ThemePickerState
     pub original_theme_path: Option<PathBuf>,
 }
 
+/// Files under `<config>/templates` offered by `file:new_from_template`.
+#[derive(Debug, Clone)]
+pub struct TemplatePickerState
+{
+    pub entries:  Vec<PathBuf>,
+    pub selected: usize,
+}
+
 #[derive(Debug, Clone)]
 pub enum Overlay
 {
@@ -51,6 +74,7 @@ pub enum Overlay
         lines: Vec<String>,
     },
     ThemePicker(Box<ThemePickerState>),
+    TemplatePicker(Box<TemplatePickerState>),
     Prompt(Box<PromptState>),
     Confirm(Box<ConfirmState>),
     CommandPane(Box<CommandPaneState>),
@@ -60,6 +84,9 @@ pub enum Overlay
 pub struct PreviewState
 {
     pub static_lines: Vec<String>,
+    // Last file shown, kept on screen for directories with
+    // `ui.preview_keep_file`
+    pub last_file:    Option<std::path::PathBuf>,
     pub cache_key:    Option<(std::path::PathBuf, u16, u16)>,
     pub cache_lines:  Option<Vec<String>>,
 }
@@ -86,6 +113,13 @@ pub enum PromptKind
 {
     AddEntry,
     MarkAdd,
+    GotoPath,
+    GotoLine,
+    Touch,
+    TransferTo(ClipboardOp),
+    // Destination name for a copy of this template
+    NewFromTemplate(PathBuf),
+    Filter(crate::core::filter::FilterKind),
     RenameEntry
     {
         from: std::path::PathBuf,
@@ -125,6 +159,8 @@ pub struct Clipboard
 pub enum ConfirmKind
 {
     DeleteSelected(Vec<std::path::PathBuf>),
+    /// Dispatch the action (or mapped key sequence) when confirmed.
+    RunAction(String),
 }
 
 #[derive(Debug, Clone)]
@@ -145,33 +181,99 @@ pub struct CommandPaneState
     pub show_suggestions: bool,
 }
 
+#[derive(Debug, Clone)]
+/// Saved state of a tab: location, listing options and visual anchor. The
+/// active tab's live state is held on [`App`]; its entry here is refreshed
+/// when switching away from it.
+pub struct TabState
+{
+    pub cwd:           PathBuf,
+    pub selected:      Option<String>,
+    pub flat_view:     bool,
+    pub list_filter:   Option<crate::core::filter::ListFilter>,
+    pub visual_anchor: Option<usize>,
+    pub sort_key:      crate::actions::SortKey,
+    pub sort_reverse:  bool,
+    pub info_mode:     InfoMode,
+    pub display_mode:  DisplayMode,
+}
+
 /// Mutable application state driving the three-pane UI.
 pub struct App
 {
-    pub(crate) cwd:               PathBuf,
-    pub(crate) current_entries:   Vec<DirEntryInfo>,
-    pub(crate) parent_entries:    Vec<DirEntryInfo>,
-    pub(crate) list_state:        ListState,
-    pub(crate) preview:           PreviewState,
-    pub(crate) recent_messages:   Vec<String>,
-    pub(crate) overlay:           Overlay,
-    pub(crate) config:            crate::config::Config,
-    pub(crate) keys:              KeyState,
-    pub(crate) force_full_redraw: bool,
-    pub(crate) lua:               Option<LuaRuntime>,
-    pub(crate) selected:          std::collections::HashSet<std::path::PathBuf>,
-    pub(crate) clipboard:         Option<Clipboard>,
-    pub(crate) sort_key:          crate::actions::SortKey,
-    pub(crate) sort_reverse:      bool,
-    pub(crate) info_mode:         InfoMode,
-    pub(crate) display_mode:      DisplayMode,
-    pub(crate) should_quit:       bool,
-    pub(crate) search_query:      Option<String>,
-    pub(crate) _search_locked:    bool,
+    pub(crate) cwd:                   PathBuf,
+    pub(crate) current_entries:       Vec<DirEntryInfo>,
+    pub(crate) parent_entries:        Vec<DirEntryInfo>,
+    pub(crate) list_state:            ListState,
+    // Parent pane scroll; its selection is the cwd's entry
+    pub(crate) parent_list_state:     ListState,
+    // Parent-pane cursor moved by `nav:parent_up`/`nav:parent_down`, as the
+    // cwd it was moved in and the entry it rests on
+    pub(crate) parent_cursor:         Option<(PathBuf, PathBuf)>,
+    // Current-pane rows shown in the last draw (`context.visible_rows`)
+    pub(crate) visible_rows:          usize,
+    pub(crate) preview:               PreviewState,
+    pub(crate) recent_messages:       Vec<String>,
+    // Rows the messages overlay is scrolled up from the newest message
+    pub(crate) messages_scroll:       usize,
+    pub(crate) overlay:               Overlay,
+    pub(crate) config:                crate::config::Config,
+    pub(crate) keys:                  KeyState,
+    pub(crate) force_full_redraw:     bool,
+    pub(crate) lua:                   Option<LuaRuntime>,
+    pub(crate) selected: std::collections::HashSet<std::path::PathBuf>,
+    // Visual mode: list index where the range being marked starts
+    pub(crate) visual_anchor:         Option<usize>,
+    pub(crate) clipboard:             Option<Clipboard>,
+    pub(crate) sort_key:              crate::actions::SortKey,
+    pub(crate) sort_reverse:          bool,
+    pub(crate) info_mode:             InfoMode,
+    pub(crate) display_mode:          DisplayMode,
+    pub(crate) should_quit:           bool,
+    // Set by `quit:discard`: skip the exit-time state write
+    pub(crate) discard_state:         bool,
+    // Marks changed but not written (the write-through failed)
+    pub(crate) marks_dirty:           bool,
+    pub(crate) search_query:          Option<String>,
+    pub(crate) _search_locked:        bool,
     pub(crate) marks: std::collections::HashMap<char, std::path::PathBuf>,
-    pub(crate) pending_mark:      bool,
-    pub(crate) pending_goto:      bool,
-    pub(crate) running_preview:   Option<RunningPreview>,
+    pub(crate) pending_mark:          bool,
+    pub(crate) pending_goto:          bool,
+    pub(crate) running_preview:       Option<RunningPreview>,
+    pub(crate) saved_panes:           Option<crate::config::UiPanes>,
+    pub(crate) watcher:               Option<crate::core::watch::DirWatcher>,
+    // Directory the watcher last failed on; not retried until the cwd changes
+    pub(crate) watch_failed:          Option<PathBuf>,
+    pub(crate) listing_stream: Option<crate::core::listing::ListingStream>,
+    // Glob/regex name filter narrowing the current listing until cleared
+    pub(crate) list_filter:           Option<crate::core::filter::ListFilter>,
+    pub(crate) theme_watcher:         Option<crate::core::watch::DirWatcher>,
+    // Theme file the watcher last failed on; not retried until it changes
+    pub(crate) theme_watch_failed:    Option<PathBuf>,
+    pub(crate) screen_mode:           crate::runtime::ScreenMode,
+    pub(crate) dir_sizes:             crate::core::dir_sizes::DirSizeCache,
+    // Commands started with lsv.os_run_async
+    pub(crate) jobs:                  crate::core::jobs::Jobs,
+    pub(crate) entry_counts: crate::core::entry_counts::EntryCountCache,
+    pub(crate) selection_size: crate::core::selection_size::SelectionSizeCache,
+    pub(crate) preview_pending_since: Option<std::time::Instant>,
+    pub(crate) preview_wrap:          bool,
+    pub(crate) flat_view:             bool,
+    pub(crate) spinner_tick:          usize,
+    pub(crate) tabs:                  Vec<TabState>,
+    pub(crate) active_tab:            usize,
+    pub(crate) registers: std::collections::HashMap<char, Clipboard>,
+    pub(crate) show_info_column:      bool,
+    // Exit code of the last os_run/interactive command or opener (-1 when
+    // killed by a signal or failed to start)
+    pub(crate) last_exit:             Option<i32>,
+    // Last selected entry name per directory, restored on re-entry
+    pub(crate) dir_selection: std::collections::HashMap<PathBuf, String>,
+    // Text most recently copied to the system clipboard
+    pub(crate) last_yank:             Option<String>,
+    // Why the config last failed to load, until a load succeeds
+    pub(crate) config_error:          Option<String>,
+    pub(crate) search_case:           CaseMode,
 }
 
 pub struct RunningPreview
@@ -186,6 +288,60 @@ pub enum InfoMode
     Size,
     Created,
     Modified,
+    Entries,
+}
+
+/// How the find pattern's letter case is compared with entry names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseMode
+{
+    /// Case-insensitive unless the pattern contains an uppercase letter
+    Smart,
+    Sensitive,
+    Insensitive,
+}
+
+impl CaseMode
+{
+    /// Whether `pat` is compared case-sensitively under this mode.
+    pub(crate) fn is_sensitive(
+        self,
+        pat: &str,
+    ) -> bool
+    {
+        match self
+        {
+            CaseMode::Smart => pat.chars().any(char::is_uppercase),
+            CaseMode::Sensitive => true,
+            CaseMode::Insensitive => false,
+        }
+    }
+
+    pub(crate) fn matches(
+        self,
+        name: &str,
+        pat: &str,
+    ) -> bool
+    {
+        if self.is_sensitive(pat)
+        {
+            name.contains(pat)
+        }
+        else
+        {
+            name.to_lowercase().contains(&pat.to_lowercase())
+        }
+    }
+
+    pub(crate) fn next(self) -> Self
+    {
+        match self
+        {
+            CaseMode::Smart => CaseMode::Sensitive,
+            CaseMode::Sensitive => CaseMode::Insensitive,
+            CaseMode::Insensitive => CaseMode::Smart,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
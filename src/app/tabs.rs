@@ -0,0 +1,112 @@
+//! Tabs for App: several working directories kept open side by side.
+
+use crate::app::{
+    App,
+    TabState,
+};
+
+impl App
+{
+    /// Capture the live state of the active tab.
+    pub(crate) fn snapshot_tab(&self) -> TabState
+    {
+        TabState {
+            cwd:           self.cwd.clone(),
+            selected:      self.selected_entry().map(|e| e.name.clone()),
+            flat_view:     self.flat_view,
+            list_filter:   self.list_filter.clone(),
+            visual_anchor: self.visual_anchor,
+            sort_key:      self.sort_key,
+            sort_reverse:  self.sort_reverse,
+            info_mode:     self.info_mode,
+            display_mode:  self.display_mode,
+        }
+    }
+
+    /// Load a saved tab into the live listing, reselecting its entry (or the
+    /// first one when it is gone).
+    fn restore_tab(
+        &mut self,
+        tab: TabState,
+    )
+    {
+        self.flat_view = tab.flat_view;
+        self.list_filter = tab.list_filter;
+        self.sort_key = tab.sort_key;
+        self.sort_reverse = tab.sort_reverse;
+        self.info_mode = tab.info_mode;
+        self.display_mode = tab.display_mode;
+        self.enter_dir_selecting(&tab.cwd, tab.selected);
+        self.visual_anchor =
+            tab.visual_anchor.filter(|&i| i < self.current_entries.len());
+        self.force_full_redraw = true;
+    }
+
+    /// Open a new tab on the current directory and switch to it.
+    pub(crate) fn new_tab(&mut self)
+    {
+        let snap = self.snapshot_tab();
+        self.tabs[self.active_tab] = snap.clone();
+        self.active_tab += 1;
+        self.tabs.insert(self.active_tab, snap);
+        self.force_full_redraw = true;
+    }
+
+    /// Close the active tab; the last remaining tab cannot be closed.
+    pub(crate) fn close_tab(&mut self)
+    {
+        if self.tabs.len() <= 1
+        {
+            self.add_message("Cannot close the last tab");
+            return;
+        }
+        self.tabs.remove(self.active_tab);
+        self.active_tab = self.active_tab.min(self.tabs.len() - 1);
+        let tab = self.tabs[self.active_tab].clone();
+        self.restore_tab(tab);
+    }
+
+    /// Move `delta` tabs forward (negative for backward), wrapping around.
+    pub(crate) fn cycle_tab(
+        &mut self,
+        delta: isize,
+    )
+    {
+        let len = self.tabs.len();
+        if len <= 1
+        {
+            return;
+        }
+        self.tabs[self.active_tab] = self.snapshot_tab();
+        self.active_tab = (self.active_tab as isize + delta)
+            .rem_euclid(len as isize) as usize;
+        let tab = self.tabs[self.active_tab].clone();
+        self.restore_tab(tab);
+    }
+
+    /// Labels for the tab bar: the directory name of each tab, using the live
+    /// cwd for the active one.
+    pub(crate) fn tab_labels(&self) -> Vec<String>
+    {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let cwd = if i == self.active_tab { &self.cwd } else { &t.cwd };
+                cwd.file_name()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| cwd.display().to_string())
+            })
+            .collect()
+    }
+
+    pub fn get_tab_count(&self) -> usize
+    {
+        self.tabs.len()
+    }
+
+    pub fn get_active_tab(&self) -> usize
+    {
+        self.active_tab
+    }
+}
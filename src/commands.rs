@@ -12,12 +12,14 @@ pub fn all() -> &'static [&'static str]
         "delete_marks",
         "delete_selected",
         "goto_mark",
+        "reload_theme",
         "rename_selected",
         "reverse_sort",
         "search_next",
         "search_prev",
         "search_text",
         "show_marks",
+        "sort_accessed_date",
         "sort_created_date",
         "sort_modified_date",
         "sort_name",
@@ -25,8 +27,10 @@ pub fn all() -> &'static [&'static str]
         "toggle_current_selected",
         "toggle_hidden_files",
         "toggle_messages",
+        "toggle_octal_permissions",
         "toggle_output",
         "view_friendly_units",
         "view_precise_units",
+        "visual_select",
     ]
 }
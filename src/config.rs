@@ -17,7 +17,9 @@ pub use types::*;
 mod paths;
 pub use paths::{
     ConfigPaths,
+    config_paths_report,
     discover_config_paths,
+    find_project_config,
 };
 mod lsv_api;
 pub(crate) use lsv_api::install_lsv_api;
@@ -33,9 +35,16 @@ mod lua_engine;
 pub use lua_engine::LuaEngine;
 mod loader;
 pub mod runtime;
-pub use loader::load_config;
+pub use loader::{
+    ConfigArtifacts,
+    ConfigError,
+    load_config,
+};
 #[allow(unused_imports)]
-pub use loader::load_config_from_code;
+pub use loader::{
+    load_config_from_code,
+    load_config_with_project,
+};
 
 pub mod defaults;
 
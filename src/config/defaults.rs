@@ -14,196 +14,111 @@ use super::{
 pub fn rust_default_keymaps() -> Vec<KeyMapping>
 {
     vec![
-        KeyMapping {
-            sequence:    "q".into(),
-            action:      "quit".into(),
-            description: Some("Quit lsv".into()),
-        },
+        map("q", "quit", "Quit lsv"),
         // Sorting
-        KeyMapping {
-            sequence:    "sn".into(),
-            action:      "sort:name".into(),
-            description: Some("Sort by name".into()),
-        },
-        KeyMapping {
-            sequence:    "ss".into(),
-            action:      "sort:size".into(),
-            description: Some("Sort by size".into()),
-        },
-        KeyMapping {
-            sequence:    "sr".into(),
-            action:      "sort:reverse:toggle".into(),
-            description: Some("Toggle reverse sort".into()),
-        },
-        KeyMapping {
-            sequence:    "sm".into(),
-            action:      "sort:mtime".into(),
-            description: Some("Sort by modified time".into()),
-        },
-        KeyMapping {
-            sequence:    "sc".into(),
-            action:      "sort:created".into(),
-            description: Some("Sort by created time".into()),
-        },
+        map("sn", "sort:name", "Sort by name"),
+        map("ss", "sort:size", "Sort by size"),
+        map("sr", "sort:reverse:toggle", "Toggle reverse sort"),
+        map("sm", "sort:mtime", "Sort by modified time"),
+        map("sc", "sort:created", "Sort by created time"),
+        map("sa", "sort:atime", "Sort by access time"),
         // Navigation
-        KeyMapping {
-            sequence:    "gg".into(),
-            action:      "nav:top".into(),
-            description: Some("Go to top".into()),
-        },
-        KeyMapping {
-            sequence:    "G".into(),
-            action:      "nav:bottom".into(),
-            description: Some("Go to bottom".into()),
-        },
+        map("gg", "nav:top", "Go to top"),
+        map("G", "nav:bottom", "Go to bottom"),
+        map("gd", "nav:first_dir", "Go to first directory"),
+        map("gD", "nav:last_dir", "Go to last directory"),
+        map("gf", "nav:first_file", "Go to first file"),
+        map("gF", "nav:last_file", "Go to last file"),
+        map("-", "nav:updir", "Go to parent directory"),
+        map("gl", "nav:follow_symlink", "Go to symlink target"),
+        map("gp", "goto:path", "Go to path"),
+        map("gn", "nav:goto_line", "Go to line number"),
+        // Tabs
+        map("tn", "tab:new", "New tab"),
+        map("tx", "tab:close", "Close tab"),
+        map("gt", "tab:next", "Next tab"),
+        map("gT", "tab:prev", "Previous tab"),
         // Vim-style navigation
-        KeyMapping {
-            sequence:    "h".into(),
-            action:      "nav:parent".into(),
-            description: Some("Go to parent".into()),
-        },
-        KeyMapping {
-            sequence:    "j".into(),
-            action:      "nav:down".into(),
-            description: Some("Move down".into()),
-        },
-        KeyMapping {
-            sequence:    "k".into(),
-            action:      "nav:up".into(),
-            description: Some("Move up".into()),
-        },
-        KeyMapping {
-            sequence:    "l".into(),
-            action:      "nav:enter".into(),
-            description: Some("Enter directory".into()),
-        },
+        map("h", "nav:parent", "Go to parent"),
+        map("j", "nav:down", "Move down"),
+        map("k", "nav:up", "Move up"),
+        map("l", "nav:enter", "Enter directory"),
+        map("K", "nav:parent_up", "Move up in the parent pane"),
+        map("J", "nav:parent_down", "Move down in the parent pane"),
         // Marks
-        KeyMapping {
-            sequence:    "m".into(),
-            action:      "marks:add_wait".into(),
-            description: Some("Set mark".into()),
-        },
-        KeyMapping {
-            sequence:    "'".into(),
-            action:      "marks:goto_wait".into(),
-            description: Some("Jump to mark".into()),
-        },
+        map("m", "marks:add_wait", "Set mark"),
+        map("'", "marks:goto_wait", "Jump to mark"),
         // Info/Display
-        KeyMapping {
-            sequence:    "zn".into(),
-            action:      "show:none".into(),
-            description: Some("Info: none".into()),
-        },
-        KeyMapping {
-            sequence:    "zs".into(),
-            action:      "show:size".into(),
-            description: Some("Info: size".into()),
-        },
-        KeyMapping {
-            sequence:    "zc".into(),
-            action:      "show:created".into(),
-            description: Some("Info: created date".into()),
-        },
-        KeyMapping {
-            sequence:    "zf".into(),
-            action:      "display:friendly".into(),
-            description: Some("Display: friendly".into()),
-        },
-        KeyMapping {
-            sequence:    "za".into(),
-            action:      "display:absolute".into(),
-            description: Some("Display: absolute".into()),
-        },
+        map("zn", "show:none", "Info: none"),
+        map("zs", "show:size", "Info: size"),
+        map("zc", "show:created", "Info: created date"),
+        map("ze", "show:entries", "Info: directory entry count"),
+        map("zf", "display:friendly", "Display: friendly"),
+        map("za", "display:absolute", "Display: absolute"),
         // Show hidden toggle and overlays
-        KeyMapping {
-            sequence:    "zh".into(),
-            action:      "cmd:toggle_hidden_files".into(),
-            description: Some("Toggle Show Hidden".into()),
-        },
-        KeyMapping {
-            sequence:    "zm".into(),
-            action:      "cmd:toggle_messages".into(),
-            description: Some("Show Messages".into()),
-        },
-        KeyMapping {
-            sequence:    "zo".into(),
-            action:      "cmd:toggle_output".into(),
-            description: Some("Show Output".into()),
-        },
+        map("zh", "toggle:hidden", "Toggle Show Hidden"),
+        map("zd", "toggle:dir_slash", "Toggle directory slash"),
+        map("zI", "toggle:info_column", "Toggle info column"),
+        map("zi", "info:counts", "Show entry counts"),
+        map("zp", "preview:toggle", "Toggle preview pane"),
+        map("zF", "flat:toggle", "Toggle flat view"),
+        map("zt", "tree:show", "Show directory tree"),
+        map("zw", "preview:toggle_wrap", "Toggle preview wrap"),
+        map("<C-r>", "preview:refresh", "Refresh preview"),
+        map("zP", "cmd:toggle_octal_permissions", "Toggle octal permissions"),
+        map("zm", "cmd:toggle_messages", "Show Messages"),
+        map("zo", "cmd:toggle_output", "Show Output"),
         // Find
-        KeyMapping {
-            sequence:    "/".into(),
-            action:      "cmd:search_text".into(),
-            description: Some("Find in current".into()),
-        },
-        KeyMapping {
-            sequence:    "n".into(),
-            action:      "cmd:search_next".into(),
-            description: Some("Find next".into()),
-        },
-        KeyMapping {
-            sequence:    "b".into(),
-            action:      "cmd:search_prev".into(),
-            description: Some("Find previous".into()),
-        },
+        map("/", "cmd:search_text", "Find in current"),
+        map("n", "cmd:search_next", "Find next"),
+        map("b", "cmd:search_prev", "Find previous"),
+        map("zC", "filter:toggle_case", "Cycle find case mode"),
         // Theme picker
-        KeyMapping {
-            sequence:    "Ut".into(),
-            action:      "cmd:change_theme".into(),
-            description: Some("UI Theme picker".into()),
-        },
+        map("Ut", "cmd:change_theme", "UI Theme picker"),
+        map("Ur", "theme:reload", "Reload theme file"),
         // File ops
-        KeyMapping {
-            sequence:    "a".into(),
-            action:      "cmd:add_item".into(),
-            description: Some("Add file/folder".into()),
-        },
-        KeyMapping {
-            sequence:    "r".into(),
-            action:      "cmd:rename_selected".into(),
-            description: Some("Rename selected".into()),
-        },
-        KeyMapping {
-            sequence:    "D".into(),
-            action:      "cmd:delete_selected".into(),
-            description: Some("Delete selected".into()),
-        },
+        map("a", "cmd:add_item", "Add file/folder"),
+        map("r", "cmd:rename_selected", "Rename selected"),
+        map("D", "cmd:delete_selected", "Delete selected"),
+        map("C", "file:duplicate", "Duplicate in place"),
+        map("N", "new:note", "New dated note"),
         // Selection
-        KeyMapping {
-            sequence:    " ".into(),
-            action:      "cmd:toggle_current_selected".into(),
-            description: Some("Toggle selected".into()),
-        },
-        KeyMapping {
-            sequence:    "u".into(),
-            action:      "cmd:clear_selected".into(),
-            description: Some("Clear selected".into()),
-        },
+        map(" ", "cmd:toggle_current_selected", "Toggle selected"),
+        map("u", "cmd:clear_selected", "Clear selected"),
+        map("<C-a>", "select:all", "Select all"),
+        map("V", "cmd:visual_select", "Visual select range"),
         // Clipboard
-        KeyMapping {
-            sequence:    "c".into(),
-            action:      "clipboard:copy".into(),
-            description: Some("Copy selected".into()),
-        },
-        KeyMapping {
-            sequence:    "x".into(),
-            action:      "clipboard:move".into(),
-            description: Some("Move selected".into()),
-        },
-        KeyMapping {
-            sequence:    "v".into(),
-            action:      "clipboard:paste".into(),
-            description: Some("Paste clipboard".into()),
-        },
+        map("c", "clipboard:copy", "Copy selected"),
+        map("x", "clipboard:move", "Move selected"),
+        map("v", "clipboard:paste", "Paste clipboard"),
+        map("X", "file:move_to", "Move selected to directory"),
+        map("Y", "file:copy_to", "Copy selected to directory"),
+        map("yp", "clipboard:yank_path", "Yank path(s) to system clipboard"),
+        map("yr", "clipboard:yank_relpath", "Yank relative path(s)"),
+        map("yn", "clipboard:yank_name", "Yank file name(s)"),
+        map("yd", "yank:dirname", "Yank directory name"),
+        map("yc", "clipboard:yank_contents", "Yank file contents"),
+        map("ys", "yank:size", "Yank size in bytes"),
         // Overlays
-        KeyMapping {
-            sequence:    "<Esc>".into(),
-            action:      "overlay:close".into(),
-            description: Some("Close overlays".into()),
-        },
+        map("<Esc>", "overlay:close", "Close overlays"),
     ]
 }
 
+/// A built-in mapping using the global `keys.sequence_timeout_ms`.
+fn map(
+    sequence: &str,
+    action: &str,
+    description: &str,
+) -> KeyMapping
+{
+    KeyMapping {
+        sequence:    sequence.into(),
+        action:      action.into(),
+        description: Some(description.into()),
+        timeout_ms:  None,
+    }
+}
+
 /// Default header templates used when the user doesn't set `ui.header`.
 pub const DEFAULT_HEADER_LEFT: &str = "{username}@{hostname}:{current_file}";
 pub const DEFAULT_HEADER_RIGHT: &str = "{current_file_size}  {owner}  \
@@ -235,26 +150,30 @@ pub fn default_row_widths() -> UiRowWidths
 pub fn default_theme() -> UiTheme
 {
     UiTheme {
-        pane_bg:               Some("#101114".into()),
-        border_fg:             Some("gray".into()),
-        item_fg:               Some("white".into()),
-        item_bg:               Some("#101114".into()),
-        selected_item_fg:      Some("black".into()),
-        selected_item_bg:      Some("cyan".into()),
-        title_fg:              Some("gray".into()),
-        title_bg:              Some("#101114".into()),
-        info_fg:               Some("gray".into()),
-        dir_fg:                Some("cyan".into()),
-        dir_bg:                Some("#101114".into()),
-        file_fg:               Some("white".into()),
-        file_bg:               Some("#101114".into()),
-        hidden_fg:             Some("darkgray".into()),
-        hidden_bg:             Some("#101114".into()),
-        exec_fg:               Some("green".into()),
-        exec_bg:               Some("#101114".into()),
-        selection_bar_fg:      Some("cyan".into()),
+        pane_bg: Some("#101114".into()),
+        border_fg: Some("gray".into()),
+        item_fg: Some("white".into()),
+        item_bg: Some("#101114".into()),
+        selected_item_fg: Some("black".into()),
+        selected_item_bg: Some("cyan".into()),
+        title_fg: Some("gray".into()),
+        title_bg: Some("#101114".into()),
+        info_fg: Some("gray".into()),
+        dir_fg: Some("cyan".into()),
+        dir_bg: Some("#101114".into()),
+        file_fg: Some("white".into()),
+        file_bg: Some("#101114".into()),
+        hidden_fg: Some("darkgray".into()),
+        hidden_bg: Some("#101114".into()),
+        exec_fg: Some("green".into()),
+        exec_bg: Some("#101114".into()),
+        denied_fg: Some("red".into()),
+        symlink_fg: Some("magenta".into()),
+        symlink_broken_fg: Some("red".into()),
+        selection_bar_fg: Some("cyan".into()),
         selection_bar_copy_fg: Some("green".into()),
         selection_bar_move_fg: Some("yellow".into()),
+        ..UiTheme::default()
     }
 }
 
@@ -14,13 +14,33 @@ use super::{
     LuaEngine,
 };
 
-type ConfigArtifacts = (
+/// Loaded configuration, keymaps and (optionally) the Lua engine together
+/// with the previewer and action registry keys.
+pub type ConfigArtifacts = (
     Config,
     Vec<KeyMapping>,
     Option<(LuaEngine, RegistryKey, Vec<RegistryKey>)>,
 );
 
+/// Load the global config and, when present, a project-local `.lsv.lua`
+/// found from the current directory upwards.
 pub fn load_config(paths: &ConfigPaths) -> io::Result<ConfigArtifacts>
+{
+    let project = std::env::current_dir()
+        .ok()
+        .and_then(|d| super::find_project_config(&d));
+    load_config_with_project(paths, project.as_deref())
+}
+
+/// Load the global config, then execute `project` (a project-local config
+/// file) in the same Lua state so its values merge over the global ones.
+/// `project` is skipped unless the global config sets
+/// `ui.project_config = true`. While the project file runs, `require` is
+/// restricted to its directory.
+pub fn load_config_with_project(
+    paths: &ConfigPaths,
+    project: Option<&Path>,
+) -> io::Result<ConfigArtifacts>
 {
     let engine = LuaEngine::new()
         .map_err(|e| io_err(format!("lua init failed: {e}")))?;
@@ -72,6 +92,48 @@ pub fn load_config(paths: &ConfigPaths) -> io::Result<ConfigArtifacts>
         }
     }
 
+    // Project files only run once the global config opts in
+    let project = project.filter(|p| {
+        let trusted = config_acc.borrow().ui.project_config;
+        if !trusted
+        {
+            crate::trace::log(format!(
+                "[lua] skip project config (ui.project_config is off): {}",
+                p.display()
+            ));
+        }
+        trusted
+    });
+    if let Some(project) = project
+    {
+        let code = fs::read_to_string(project).map_err(|e| {
+            io_err(format!("read {} failed: {e}", project.display()))
+        })?;
+        let project_dir = project.parent().unwrap_or_else(|| Path::new("."));
+        super::install_require(lua, project_dir)
+            .map_err(|e| io_err(format!("require install failed: {e}")))?;
+        crate::trace::log(format!(
+            "[lua] exec project config: {}",
+            project.to_string_lossy()
+        ));
+        let chunk = lua.load(&code).set_name(project.to_string_lossy());
+        if let Err(e) = chunk.exec()
+        {
+            crate::trace::log(format!(
+                "[lua] project config error ({}): {}",
+                project.to_string_lossy(),
+                e
+            ));
+            return Err(io_err(format!(
+                "{} execution failed: {e}",
+                project.display()
+            )));
+        }
+        // Restore the global module root for runtime requires
+        super::install_require(lua, &paths.root.join("lua"))
+            .map_err(|e| io_err(format!("require install failed: {e}")))?;
+    }
+
     let cfg = config_acc.borrow().clone();
     let mut cfg = cfg;
     super::defaults::apply_config_defaults(&mut cfg);
@@ -189,7 +251,24 @@ pub fn load_config_from_code(
     Ok((cfg, maps, engine_opt))
 }
 
+/// Error raised while loading or executing the Lua configuration.
+#[derive(Debug)]
+pub struct ConfigError(pub String);
+
+impl std::fmt::Display for ConfigError
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result
+    {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
 fn io_err(msg: String) -> io::Error
 {
-    io::Error::other(msg)
+    io::Error::other(ConfigError(msg))
 }
@@ -354,6 +354,26 @@ pub(crate) fn install_lsv_api(
                         &mut cfg_mut,
                     )?;
                 }
+                if let Ok(p_tbl) = t.get::<Table>("previewers")
+                {
+                    for (ext, cmd) in p_tbl.pairs::<String, String>().flatten()
+                    {
+                        cfg_mut.previewers.insert(
+                            ext.trim_start_matches('.').to_lowercase(),
+                            crate::util::expand_env_vars(&cmd),
+                        );
+                    }
+                }
+                if let Ok(o_tbl) = t.get::<Table>("openers")
+                {
+                    for (ext, cmd) in o_tbl.pairs::<String, String>().flatten()
+                    {
+                        cfg_mut.openers.insert(
+                            ext.trim_start_matches('.').to_lowercase(),
+                            crate::util::expand_env_vars(&cmd),
+                        );
+                    }
+                }
 
                 // Top-level actions table (collect both Lua fn and string
                 // actions)
@@ -374,6 +394,8 @@ pub(crate) fn install_lsv_api(
                                         LuaError::RuntimeError(e.to_string())
                                     })?;
                                 let desc = t.get::<String>("description").ok();
+                                let timeout_ms =
+                                    t.get::<u64>("timeout_ms").ok();
                                 let reg = lua
                                     .create_registry_value(func)
                                     .map_err(|e| {
@@ -383,9 +405,10 @@ pub(crate) fn install_lsv_api(
                                 actions_in_cfg.borrow_mut().push(reg);
                                 maps_in_cfg.borrow_mut().push(
                                     super::KeyMapping {
-                                        sequence:    keymap,
-                                        action:      format!("run_lua:{}", idx),
+                                        sequence: keymap,
+                                        action: format!("run_lua:{}", idx),
                                         description: desc,
+                                        timeout_ms,
                                     },
                                 );
                                 continue;
@@ -402,6 +425,9 @@ pub(crate) fn install_lsv_api(
                                         sequence:    kseq,
                                         action:      action_str,
                                         description: desc,
+                                        timeout_ms:  t
+                                            .get::<u64>("timeout_ms")
+                                            .ok(),
                                     },
                                 );
                             }
@@ -422,6 +448,7 @@ pub(crate) fn install_lsv_api(
                     sequence: seq,
                     action,
                     description: desc,
+                    timeout_ms: None,
                 });
                 Ok(true)
             },
@@ -438,17 +465,21 @@ pub(crate) fn install_lsv_api(
         })
         .map_err(|e| io::Error::other(e.to_string()))?;
 
-    // lsv.map_action(keymap_or_list, description, fn)
+    // lsv.map_action(keymap_or_list, description, fn, opts?)
+    // opts: { timeout_ms = n }
     let actions_acc_outer = Rc::clone(&lua_action_keys_out);
     let maps_for_actions_outer = Rc::clone(&maps);
     let map_action_fn = lua
         .create_function(
             move |lua,
-                  (keymaps_val, desc, func): (
+                  (keymaps_val, desc, func, opts): (
                 Value,
                 String,
                 mlua::Function,
+                Option<Table>,
             )| {
+                let timeout_ms =
+                    opts.and_then(|o| o.get::<u64>("timeout_ms").ok());
                 let reg = lua.create_registry_value(func)?;
                 let idx = actions_acc_outer.borrow().len();
                 actions_acc_outer.borrow_mut().push(reg);
@@ -463,9 +494,10 @@ pub(crate) fn install_lsv_api(
                             .unwrap_or_default();
                         maps_for_actions_outer.borrow_mut().push(
                             super::KeyMapping {
-                                sequence:    seq,
-                                action:      action_str.clone(),
+                                sequence: seq,
+                                action: action_str.clone(),
                                 description: Some(desc.clone()),
+                                timeout_ms,
                             },
                         );
                     }
@@ -481,9 +513,10 @@ pub(crate) fn install_lsv_api(
                                     .unwrap_or_default();
                                 maps_for_actions_outer.borrow_mut().push(
                                     super::KeyMapping {
-                                        sequence:    seq,
-                                        action:      action_str.clone(),
+                                        sequence: seq,
+                                        action: action_str.clone(),
                                         description: Some(desc.clone()),
+                                        timeout_ms,
                                     },
                                 );
                             }
@@ -497,6 +530,67 @@ pub(crate) fn install_lsv_api(
         )
         .map_err(|e| io::Error::other(e.to_string()))?;
 
+    // lsv.unmap(keymap_or_list): drop bindings (defaults included) for the
+    // given sequences; returns true when anything was removed
+    let maps_for_unmap = Rc::clone(&maps);
+    let unmap_fn = lua
+        .create_function(move |_, keymaps_val: Value| {
+            let mut seqs: Vec<String> = Vec::new();
+            match keymaps_val
+            {
+                Value::String(s) => seqs.push(s.to_str()?.to_string()),
+                Value::Table(t) =>
+                {
+                    for v in t.sequence_values::<String>()
+                    {
+                        seqs.push(v?);
+                    }
+                }
+                _ =>
+                {}
+            }
+            let targets: Vec<String> = seqs
+                .iter()
+                .map(|s| crate::keymap::tokenize_sequence(s).concat())
+                .collect();
+            let mut list = maps_for_unmap.borrow_mut();
+            let before = list.len();
+            list.retain(|m| {
+                let seq =
+                    crate::keymap::tokenize_sequence(&m.sequence).concat();
+                !targets.contains(&seq)
+            });
+            Ok(list.len() != before)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    // lsv.on_enter(fn): hook run before `nav:enter` changes directory
+    let config_for_enter = Rc::clone(&config_acc);
+    let actions_for_enter = Rc::clone(&lua_action_keys_out);
+    let on_enter_fn = lua
+        .create_function(move |lua, func: mlua::Function| {
+            let reg = lua.create_registry_value(func)?;
+            let idx = actions_for_enter.borrow().len();
+            actions_for_enter.borrow_mut().push(reg);
+            config_for_enter.borrow_mut().on_enter = Some(idx);
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    // lsv.map_group(prefix, label): name a which-key group
+    let config_for_groups = Rc::clone(&config_acc);
+    let map_group_fn = lua
+        .create_function(move |_, (prefix, label): (String, String)| {
+            let key = crate::keymap::tokenize_sequence(&prefix).concat();
+            if key.is_empty()
+            {
+                return Ok(false);
+            }
+            config_for_groups.borrow_mut().keys.groups.insert(key, label);
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
     // Wire helpers
     lsv.set("config", config_fn)
         .map_err(|e| io::Error::other(e.to_string()))?;
@@ -506,6 +600,11 @@ pub(crate) fn install_lsv_api(
         .map_err(|e| io::Error::other(e.to_string()))?;
     lsv.set("map_action", map_action_fn)
         .map_err(|e| io::Error::other(e.to_string()))?;
+    lsv.set("unmap", unmap_fn).map_err(|e| io::Error::other(e.to_string()))?;
+    lsv.set("map_group", map_group_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    lsv.set("on_enter", on_enter_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
 
     // lsv.quote
     let quote_fn = lua
@@ -690,7 +789,10 @@ fn merge_ui_table(
                 "ui.theme_path must be a non-empty string".to_string(),
             ));
         }
-        let resolved_path = resolve_theme_path(&theme_path_str, theme_root);
+        let resolved_path = resolve_theme_path(
+            &crate::util::expand_env_vars(&theme_path_str),
+            theme_root,
+        );
         let theme_tbl = load_theme_table_from_path(lua, &resolved_path)?;
         let mut th = cfg_mut.ui.theme.clone().unwrap_or_default();
         merge_theme_table(&theme_tbl, &mut th);
@@ -707,6 +809,10 @@ fn merge_ui_table(
                 let mut th = cfg_mut.ui.theme.clone().unwrap_or_default();
                 merge_theme_table(&theme_tbl, &mut th);
                 cfg_mut.ui.theme = Some(th);
+                let mut top =
+                    cfg_mut.ui.theme_overlay.clone().unwrap_or_default();
+                merge_theme_table(&theme_tbl, &mut top);
+                cfg_mut.ui.theme_overlay = Some(top);
             }
             Value::String(s) =>
             {
@@ -768,6 +874,155 @@ fn merge_ui_table(
     {
         cfg_mut.ui.confirm_delete = b;
     }
+    if let Ok(b) = ui_tbl.get::<bool>("auto_refresh")
+    {
+        cfg_mut.ui.auto_refresh = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("dir_sizes")
+    {
+        cfg_mut.ui.dir_sizes = b;
+    }
+    if let Ok(n) = ui_tbl.get::<u64>("preview_debounce_ms")
+    {
+        cfg_mut.ui.preview_debounce_ms = n;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("permissions_octal")
+    {
+        cfg_mut.ui.permissions_octal = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("syntax_highlight")
+    {
+        cfg_mut.ui.syntax_highlight = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("preview_line_numbers")
+    {
+        cfg_mut.ui.preview_line_numbers = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("preview_squeeze_blank")
+    {
+        cfg_mut.ui.preview_squeeze_blank = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("preview_wrap")
+    {
+        cfg_mut.ui.preview_wrap = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("clipboard_tool")
+    {
+        cfg_mut.ui.clipboard_tool = b;
+    }
+    if let Ok(s) = ui_tbl.get::<String>("command_cwd")
+    {
+        cfg_mut.ui.command_cwd = Some(s);
+    }
+    if let Ok(s) = ui_tbl.get::<String>("border_style")
+    {
+        cfg_mut.ui.border_style = Some(s);
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("show_spinner")
+    {
+        cfg_mut.ui.show_spinner = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("preview_keep_file")
+    {
+        cfg_mut.ui.preview_keep_file = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("show_selection_path")
+    {
+        cfg_mut.ui.show_selection_path = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("project_config")
+    {
+        cfg_mut.ui.project_config = b;
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("dir_slash")
+    {
+        cfg_mut.ui.dir_slash = b;
+    }
+    if let Ok(n) = ui_tbl.get::<u64>("scrolloff")
+    {
+        cfg_mut.ui.scrolloff = n as usize;
+    }
+    if let Ok(s) = ui_tbl.get::<String>("preview_follows")
+    {
+        match s.trim().to_ascii_lowercase().as_str()
+        {
+            "current" | "parent" => cfg_mut.ui.preview_follows = Some(s),
+            _ =>
+            {
+                return Err(LuaError::RuntimeError(format!(
+                    "ui.preview_follows must be \"current\" or \"parent\" \
+                     (got \"{}\")",
+                    s
+                )));
+            }
+        }
+    }
+    if let Ok(s) = ui_tbl.get::<String>("dir_preview")
+    {
+        match s.trim().to_ascii_lowercase().as_str()
+        {
+            "flat" | "tree" => cfg_mut.ui.dir_preview = Some(s),
+            _ =>
+            {
+                return Err(LuaError::RuntimeError(format!(
+                    "ui.dir_preview must be \"flat\" or \"tree\" (got \"{}\")",
+                    s
+                )));
+            }
+        }
+    }
+    if let Ok(s) = ui_tbl.get::<String>("search_case")
+    {
+        if crate::enums::case_mode_from_str(&s).is_none()
+        {
+            return Err(LuaError::RuntimeError(format!(
+                "ui.search_case must be \"smart\", \"sensitive\" or \
+                 \"insensitive\" (got \"{}\")",
+                s
+            )));
+        }
+        cfg_mut.ui.search_case = Some(s);
+    }
+    match ui_tbl.get::<Value>("line_numbers")
+    {
+        Ok(Value::Boolean(b)) =>
+        {
+            cfg_mut.ui.line_numbers = b.then(|| "absolute".to_string());
+        }
+        Ok(Value::String(s)) =>
+        {
+            let s = s.to_string_lossy().trim().to_ascii_lowercase();
+            match s.as_str()
+            {
+                "absolute" | "relative" => cfg_mut.ui.line_numbers = Some(s),
+                _ =>
+                {
+                    return Err(LuaError::RuntimeError(format!(
+                        "ui.line_numbers must be a boolean, \"absolute\" or \
+                         \"relative\" (got \"{}\")",
+                        s
+                    )));
+                }
+            }
+        }
+        _ =>
+        {}
+    }
+    if let Ok(b) = ui_tbl.get::<bool>("bulk_report")
+    {
+        cfg_mut.ui.bulk_report = b;
+    }
+    if let Ok(s) = ui_tbl.get::<String>("note_template")
+        && !s.trim().is_empty()
+    {
+        cfg_mut.ui.note_template = Some(s);
+    }
+    if let Ok(s) = ui_tbl.get::<String>("notes_dir")
+        && !s.trim().is_empty()
+    {
+        cfg_mut.ui.notes_dir =
+            Some(std::path::PathBuf::from(crate::util::expand_env_vars(&s)));
+    }
     if let Ok(modals_tbl) = ui_tbl.get::<Table>("modals")
     {
         let mut modals = cfg_mut.ui.modals.clone().unwrap_or_default();
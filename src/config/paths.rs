@@ -7,6 +7,9 @@ use std::{
     },
 };
 
+/// File name of the per-project config searched for from the cwd upwards.
+pub const PROJECT_CONFIG_FILE: &str = ".lsv.lua";
+
 /// Resolved configuration locations for lsv.
 #[derive(Debug, Clone)]
 pub struct ConfigPaths
@@ -28,6 +31,41 @@ pub struct ConfigPaths
 /// The returned struct includes the root directory, the path to `init.lua`, and
 /// whether the file currently exists.
 pub fn discover_config_paths() -> std::io::Result<ConfigPaths>
+{
+    let (root, _) = resolve_root();
+    let entry = root.join("init.lua");
+    let exists = fs::metadata(&entry).map(|m| m.is_file()).unwrap_or(false);
+    Ok(ConfigPaths { root, entry, exists })
+}
+
+/// Human-readable summary of where configuration is loaded from: the
+/// resolved root and which rule chose it, `init.lua` and whether it exists,
+/// and the project-local config found from `cwd`, if any.
+pub fn config_paths_report(cwd: &Path) -> std::io::Result<String>
+{
+    let (_, source) = resolve_root();
+    let paths = discover_config_paths()?;
+    let mut out = vec![
+        format!("root:    {} ({})", paths.root.display(), source),
+        format!(
+            "init:    {} ({})",
+            paths.entry.display(),
+            if paths.exists { "exists" } else { "missing" }
+        ),
+    ];
+    match find_project_config(cwd)
+    {
+        Some(p) => out.push(format!("project: {}", p.display())),
+        None => out.push(format!("project: none ({})", PROJECT_CONFIG_FILE)),
+    }
+    out.push(String::from(
+        "order:   LSV_CONFIG_DIR > $XDG_CONFIG_HOME/lsv > platform default",
+    ));
+    Ok(out.join("\n"))
+}
+
+/// Pick the config root and name the rule that selected it.
+fn resolve_root() -> (PathBuf, &'static str)
 {
     fn root_from_env() -> Option<PathBuf>
     {
@@ -39,54 +77,77 @@ pub fn discover_config_paths() -> std::io::Result<ConfigPaths>
         None
     }
 
-    let root = if let Some(over) = root_from_env()
+    if let Some(over) = root_from_env()
     {
-        over
+        (over, "LSV_CONFIG_DIR")
     }
     else if let Ok(xdg) = env::var("XDG_CONFIG_HOME")
         && !xdg.trim().is_empty()
     {
-        Path::new(&xdg).join("lsv")
+        (Path::new(&xdg).join("lsv"), "XDG_CONFIG_HOME")
     }
     else
     {
-        #[cfg(windows)]
+        (default_root(), "default")
+    }
+}
+
+fn default_root() -> PathBuf
+{
+    #[cfg(windows)]
+    {
+        if let Ok(local) = env::var("LOCALAPPDATA")
+            && !local.trim().is_empty()
         {
-            if let Ok(local) = env::var("LOCALAPPDATA")
-                && !local.trim().is_empty()
-            {
-                Path::new(&local).join("lsv")
-            }
-            else if let Ok(app) = env::var("APPDATA")
-                && !app.trim().is_empty()
-            {
-                Path::new(&app).join("lsv")
-            }
-            else if let Ok(up) = env::var("USERPROFILE")
-                && !up.trim().is_empty()
-            {
-                Path::new(&up).join(".config").join("lsv")
-            }
-            else
-            {
-                Path::new(".config").join("lsv")
-            }
+            Path::new(&local).join("lsv")
         }
-        #[cfg(not(windows))]
+        else if let Ok(app) = env::var("APPDATA")
+            && !app.trim().is_empty()
         {
-            if let Ok(home) = env::var("HOME")
-                && !home.trim().is_empty()
-            {
-                Path::new(&home).join(".config").join("lsv")
-            }
-            else
-            {
-                Path::new(".config").join("lsv")
-            }
+            Path::new(&app).join("lsv")
         }
-    };
+        else if let Ok(up) = env::var("USERPROFILE")
+            && !up.trim().is_empty()
+        {
+            Path::new(&up).join(".config").join("lsv")
+        }
+        else
+        {
+            Path::new(".config").join("lsv")
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        if let Ok(home) = env::var("HOME")
+            && !home.trim().is_empty()
+        {
+            Path::new(&home).join(".config").join("lsv")
+        }
+        else
+        {
+            Path::new(".config").join("lsv")
+        }
+    }
+}
 
-    let entry = root.join("init.lua");
-    let exists = fs::metadata(&entry).map(|m| m.is_file()).unwrap_or(false);
-    Ok(ConfigPaths { root, entry, exists })
+/// Find a project-local [`PROJECT_CONFIG_FILE`] starting at `start` and
+/// walking up through its ancestors. The search stops at the first directory
+/// containing `.git`, which is treated as the project root, and at the home
+/// directory.
+pub fn find_project_config(start: &Path) -> Option<PathBuf>
+{
+    let home = crate::util::home_dir().map(PathBuf::from);
+    for dir in start.ancestors()
+    {
+        let cand = dir.join(PROJECT_CONFIG_FILE);
+        if fs::metadata(&cand).map(|m| m.is_file()).unwrap_or(false)
+        {
+            return Some(cand);
+        }
+        if dir.join(".git").exists() || home.as_deref() == Some(dir)
+        {
+            break;
+        }
+    }
+    None
 }
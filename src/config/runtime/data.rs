@@ -52,9 +52,22 @@ pub struct UiThemeData
     pub hidden_bg:             Option<String>,
     pub exec_fg:               Option<String>,
     pub exec_bg:               Option<String>,
+    pub denied_fg:             Option<String>,
+    pub symlink_fg:            Option<String>,
+    pub symlink_broken_fg:     Option<String>,
     pub selection_bar_fg:      Option<String>,
     pub selection_bar_copy_fg: Option<String>,
     pub selection_bar_move_fg: Option<String>,
+    pub selection_bar_copy_bg: Option<String>,
+    pub selection_bar_move_bg: Option<String>,
+    pub clipboard_copy_glyph:  Option<String>,
+    pub clipboard_move_glyph:  Option<String>,
+    pub item_mods:             Option<Vec<String>>,
+    pub selected_item_mods:    Option<Vec<String>>,
+    pub dir_mods:              Option<Vec<String>>,
+    pub file_mods:             Option<Vec<String>>,
+    pub hidden_mods:           Option<Vec<String>>,
+    pub exec_mods:             Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone)]
@@ -76,6 +89,7 @@ pub struct UiData
     pub display_mode:   crate::app::DisplayMode,
     pub max_list_items: usize,
     pub confirm_delete: bool,
+    pub scrolloff:      usize,
     pub row:            UiRowData,
     pub row_widths:     Option<crate::config::UiRowWidths>,
     pub theme_path:     Option<String>,
@@ -130,6 +144,7 @@ pub fn to_lua_config_table(
     ui.set("display_mode", display_mode_to_str(app.display_mode))?;
     ui.set("max_list_items", app.config.ui.max_list_items as u64)?;
     ui.set("confirm_delete", app.config.ui.confirm_delete)?;
+    ui.set("scrolloff", app.config.ui.scrolloff as u64)?;
 
     // context snapshot for actions
     let ctx = lua.create_table()?;
@@ -138,6 +153,20 @@ pub fn to_lua_config_table(
         app.list_state.selected().map(|i| i as u64).unwrap_or(u64::MAX);
     ctx.set("selected_index", sel_idx)?;
     ctx.set("current_len", app.current_entries.len() as u64)?;
+    ctx.set("visible_rows", app.visible_rows as u64)?;
+    // Listing snapshot from the already-loaded entries (1-based array)
+    let entries =
+        lua.create_table_with_capacity(app.current_entries.len(), 0)?;
+    for (i, e) in app.current_entries.iter().enumerate()
+    {
+        let row = lua.create_table_with_capacity(0, 4)?;
+        row.set("name", e.name.as_str())?;
+        row.set("path", e.path.to_string_lossy().to_string())?;
+        row.set("is_dir", e.is_dir)?;
+        row.set("size", e.size)?;
+        entries.raw_set(i + 1, row)?;
+    }
+    ctx.set("entries", entries)?;
     // Include commonly used path fields for convenience in actions
     use chrono::{
         DateTime,
@@ -296,6 +325,18 @@ pub fn to_lua_config_table(
         {
             theme_tbl.set("exec_bg", v.as_str())?;
         }
+        if let Some(v) = theme.denied_fg.as_ref()
+        {
+            theme_tbl.set("denied_fg", v.as_str())?;
+        }
+        if let Some(v) = theme.symlink_fg.as_ref()
+        {
+            theme_tbl.set("symlink_fg", v.as_str())?;
+        }
+        if let Some(v) = theme.symlink_broken_fg.as_ref()
+        {
+            theme_tbl.set("symlink_broken_fg", v.as_str())?;
+        }
         if let Some(v) = theme.selection_bar_fg.as_ref()
         {
             theme_tbl.set("selection_bar_fg", v.as_str())?;
@@ -308,6 +349,37 @@ pub fn to_lua_config_table(
         {
             theme_tbl.set("selection_bar_move_fg", v.as_str())?;
         }
+        if let Some(v) = theme.selection_bar_copy_bg.as_ref()
+        {
+            theme_tbl.set("selection_bar_copy_bg", v.as_str())?;
+        }
+        if let Some(v) = theme.selection_bar_move_bg.as_ref()
+        {
+            theme_tbl.set("selection_bar_move_bg", v.as_str())?;
+        }
+        if let Some(v) = theme.clipboard_copy_glyph.as_ref()
+        {
+            theme_tbl.set("clipboard_copy_glyph", v.as_str())?;
+        }
+        if let Some(v) = theme.clipboard_move_glyph.as_ref()
+        {
+            theme_tbl.set("clipboard_move_glyph", v.as_str())?;
+        }
+        let mods = [
+            ("item_mods", &theme.item_mods),
+            ("selected_item_mods", &theme.selected_item_mods),
+            ("dir_mods", &theme.dir_mods),
+            ("file_mods", &theme.file_mods),
+            ("hidden_mods", &theme.hidden_mods),
+            ("exec_mods", &theme.exec_mods),
+        ];
+        for (key, list) in mods
+        {
+            if let Some(list) = list
+            {
+                theme_tbl.set(key, lua.create_sequence_from(list.clone())?)?;
+            }
+        }
         ui.set("theme", theme_tbl)?;
     }
     if let Some(tp) = app.config.ui.theme_path.as_ref()
@@ -342,6 +414,7 @@ pub fn from_lua_config_table(tbl: Table) -> Result<ConfigData, String>
             display_mode:   crate::app::DisplayMode::Friendly,
             max_list_items: 5000,
             confirm_delete: true,
+            scrolloff:      0,
             row:            UiRowData {
                 icon:   " ".into(),
                 left:   "{name}".into(),
@@ -409,6 +482,10 @@ pub fn from_lua_config_table(tbl: Table) -> Result<ConfigData, String>
         {
             data.ui.confirm_delete = b;
         }
+        if let Ok(n) = ui.get::<u64>("scrolloff")
+        {
+            data.ui.scrolloff = n as usize;
+        }
 
         if let Ok(row) = ui.get::<Table>("row")
         {
@@ -527,6 +604,18 @@ pub fn from_lua_config_table(tbl: Table) -> Result<ConfigData, String>
             {
                 th.exec_bg = Some(v);
             }
+            if let Ok(v) = theme_tbl.get::<String>("denied_fg")
+            {
+                th.denied_fg = Some(v);
+            }
+            if let Ok(v) = theme_tbl.get::<String>("symlink_fg")
+            {
+                th.symlink_fg = Some(v);
+            }
+            if let Ok(v) = theme_tbl.get::<String>("symlink_broken_fg")
+            {
+                th.symlink_broken_fg = Some(v);
+            }
             if let Ok(v) = theme_tbl.get::<String>("selection_bar_fg")
             {
                 th.selection_bar_fg = Some(v);
@@ -539,6 +628,35 @@ pub fn from_lua_config_table(tbl: Table) -> Result<ConfigData, String>
             {
                 th.selection_bar_move_fg = Some(v);
             }
+            if let Ok(v) = theme_tbl.get::<String>("selection_bar_copy_bg")
+            {
+                th.selection_bar_copy_bg = Some(v);
+            }
+            if let Ok(v) = theme_tbl.get::<String>("selection_bar_move_bg")
+            {
+                th.selection_bar_move_bg = Some(v);
+            }
+            if let Ok(v) = theme_tbl.get::<String>("clipboard_copy_glyph")
+            {
+                th.clipboard_copy_glyph = Some(v);
+            }
+            if let Ok(v) = theme_tbl.get::<String>("clipboard_move_glyph")
+            {
+                th.clipboard_move_glyph = Some(v);
+            }
+            let mods = [
+                ("item_mods", &mut th.item_mods),
+                ("selected_item_mods", &mut th.selected_item_mods),
+                ("dir_mods", &mut th.dir_mods),
+                ("file_mods", &mut th.file_mods),
+                ("hidden_mods", &mut th.hidden_mods),
+                ("exec_mods", &mut th.exec_mods),
+            ];
+            for (key, slot) in mods
+            {
+                *slot =
+                    crate::config::theme::read_modifier_list(&theme_tbl, key);
+            }
             data.ui.theme = Some(th);
         }
     }
@@ -550,7 +668,8 @@ pub fn from_lua_config_table(tbl: Table) -> Result<ConfigData, String>
             let Some(k) = sort_key_from_str(&v)
             else
             {
-                return Err("sort.key must be one of: name|size|mtime|created"
+                return Err("sort.key must be one of: \
+                            name|size|mtime|created|atime"
                     .to_string());
             };
             data.sort_key = k;
@@ -73,7 +73,9 @@ pub fn call_lua_action(
             let bt = std::backtrace::Backtrace::force_capture();
             trace::log(format!("[lua] action idx={} error: {}", idx, e));
             trace::log(format!("[lua] backtrace:\n{}", bt));
-            io::Error::other(format!("lua fn: {e}"))
+            io::Error::other(crate::actions::ActionError(format!(
+                "lua fn: {e}"
+            )))
         })?;
     trace::log(format!(
         "[lua] action idx={} ok in {}ms",
@@ -81,6 +83,7 @@ pub fn call_lua_action(
         started.elapsed().as_millis()
     ));
 
+    let declined = matches!(ret_val, Value::Boolean(false));
     // Prefer merging any returned partial table into the full snapshot
     let candidate_tbl = match ret_val
     {
@@ -91,6 +94,7 @@ pub fn call_lua_action(
 
     // Parse lightweight effects first
     let mut fx = parse_effects_from_lua(&candidate_tbl);
+    fx.declined = declined;
     // Fallback: read from original cfg table if helper mutated it
     if fx.output.is_none()
         && let Ok(text) = cfg_tbl_copy.get::<String>("output_text")
@@ -118,7 +122,7 @@ fn build_lsv_helpers(
 
     // (UI helpers removed)
     // Selection and prompts
-    build_selection_helpers(lua, &tbl, cfg_tbl)?;
+    build_selection_helpers(lua, &tbl, cfg_tbl, app)?;
     // Clipboard helpers
     build_clipboard_helpers(lua, &tbl, cfg_tbl)?;
     // Process helpers are inlined below
@@ -181,6 +185,60 @@ fn build_lsv_helpers(
     tbl.set("force_redraw", force_redraw_fn)
         .map_err(|e| io::Error::other(e.to_string()))?;
 
+    // refresh(): re-read the listing, keeping the selected entry
+    let cfg_ref_refresh = cfg_tbl.clone();
+    let refresh_fn = lua
+        .create_function(move |_, ()| {
+            let _ = cfg_ref_refresh.set("refresh", true);
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    tbl.set("refresh", refresh_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    // open_at_line(line): open the selected file in the editor at `line`
+    let cfg_ref_line = cfg_tbl.clone();
+    let open_at_line_fn = lua
+        .create_function(move |_, line: u64| {
+            let _ = cfg_ref_line.set("open_line", line);
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    tbl.set("open_at_line", open_at_line_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    // touch(name?): bump the selection's mtime, or touch/create `name`
+    let cfg_ref_touch = cfg_tbl.clone();
+    let touch_fn = lua
+        .create_function(move |_, name: Option<String>| {
+            let _ = cfg_ref_touch.set("touch", name.unwrap_or_default());
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    tbl.set("touch", touch_fn).map_err(|e| io::Error::other(e.to_string()))?;
+
+    // reveal(path): relist (changing directory if needed) and select `path`
+    let cfg_ref_reveal = cfg_tbl.clone();
+    let reveal_fn = lua
+        .create_function(move |_, path: String| {
+            let _ = cfg_ref_reveal.set("reveal", path);
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    tbl.set("reveal", reveal_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    // toggle_hidden(): flip ui.show_hidden and relist
+    let cfg_ref_hidden = cfg_tbl.clone();
+    let toggle_hidden_fn = lua
+        .create_function(move |_, ()| {
+            let _ = cfg_ref_hidden.set("toggle_hidden", true);
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    tbl.set("toggle_hidden", toggle_hidden_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
     // clear_messages(): clear the UI message list
     let cfg_ref_cmsg = cfg_tbl.clone();
     let clear_messages_fn = lua
@@ -239,11 +297,15 @@ fn build_lsv_helpers(
         .map_err(|e| io::Error::other(e.to_string()))?;
     tbl.set("trace", trace_fn).map_err(|e| io::Error::other(e.to_string()))?;
 
-    // quit(): set quit flag in effects
+    // quit({ discard = true }?): set quit flag in effects
     let cfg_ref_quit = cfg_tbl.clone();
     let quit_fn = lua
-        .create_function(move |_, ()| {
+        .create_function(move |_, opts: Option<mlua::Table>| {
             let _ = cfg_ref_quit.set("quit", true);
+            if opts.is_some_and(|t| t.get::<bool>("discard").unwrap_or(false))
+            {
+                let _ = cfg_ref_quit.set("quit_discard", true);
+            }
             Ok(true)
         })
         .map_err(|e| io::Error::other(e.to_string()))?;
@@ -298,6 +360,30 @@ fn build_lsv_helpers(
     tbl.set("paste_clipboard", paste_clipboard_fn)
         .map_err(|e| io::Error::other(e.to_string()))?;
 
+    let cfg_ref_yp = cfg_tbl.clone();
+    let yank_path_fn = lua
+        .create_function(move |_, opts: Option<mlua::Table>| {
+            let relative = opts
+                .and_then(|t| t.get::<bool>("relative").ok())
+                .unwrap_or(false);
+            let cmd = if relative { "yank_relpath" } else { "yank_path" };
+            let _ = cfg_ref_yp.set("clipboard", cmd);
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    tbl.set("yank_path", yank_path_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    let cfg_ref_yn = cfg_tbl.clone();
+    let yank_name_fn = lua
+        .create_function(move |_, ()| {
+            let _ = cfg_ref_yn.set("clipboard", "yank_name");
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    tbl.set("yank_name", yank_name_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
     let cfg_ref_cc = cfg_tbl.clone();
     let clear_clipboard_fn = lua
         .create_function(move |_, ()| {
@@ -310,38 +396,24 @@ fn build_lsv_helpers(
 
     // os_run (captured)
     let cfg_ref5 = cfg_tbl.clone();
-    let cwd_str = app.cwd.to_string_lossy().to_string();
+    let cwd_str = app.command_dir().to_string_lossy().to_string();
     let cwd_capture = cwd_str.clone();
 
     let os_run_fn = lua
         .create_function(move |_, cmd: String| {
             trace::log(format!("[os_run] cwd='{}' cmd='{}'", cwd_capture, cmd));
-            #[cfg(windows)]
-            let mut command = {
-                let mut c = std::process::Command::new("cmd");
-                c.arg("/C").arg(&cmd);
-                c
-            };
-            #[cfg(not(windows))]
-            let mut command = {
-                let mut c = std::process::Command::new("sh");
-                c.arg("-lc").arg(&cmd);
-                c
-            };
-            let out = command.current_dir(&cwd_capture).output();
+            let out = crate::core::jobs::shell_command(&cmd)
+                .current_dir(&cwd_capture)
+                .output();
             match out
             {
                 Ok(output) =>
                 {
-                    let mut buf = Vec::new();
-                    buf.extend_from_slice(&output.stdout);
-                    if !output.stderr.is_empty()
-                    {
-                        buf.push(b'\n');
-                        buf.extend_from_slice(&output.stderr);
-                    }
+                    let buf = crate::core::jobs::combined_output(&output);
                     let bytes = buf.len();
                     let success = output.status.success();
+                    let _ = cfg_ref5
+                        .set("exit_code", output.status.code().unwrap_or(-1));
                     trace::log(format!(
                         "[os_run] exit={:?} bytes_out={}",
                         output.status.code(),
@@ -364,6 +436,7 @@ fn build_lsv_helpers(
                 Err(e) =>
                 {
                     trace::log(format!("[os_run] error: {}", e));
+                    let _ = cfg_ref5.set("exit_code", -1);
                     let text = format!("<error: {}>", e);
                     let title = format!("$ {}", cmd);
                     let _ = cfg_ref5.set("output_text", text);
@@ -376,30 +449,36 @@ fn build_lsv_helpers(
     tbl.set("os_run", os_run_fn)
         .map_err(|e| io::Error::other(e.to_string()))?;
 
+    // os_run_async: queued in effects and started once the action returns
+    let cfg_ref_async = cfg_tbl.clone();
+    let os_run_async_fn = lua
+        .create_function(move |lua, cmd: String| {
+            let list = match cfg_ref_async.get::<Table>("os_run_async")
+            {
+                Ok(t) => t,
+                Err(_) =>
+                {
+                    let t = lua.create_table()?;
+                    cfg_ref_async.set("os_run_async", t.clone())?;
+                    t
+                }
+            };
+            list.push(cmd)?;
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    tbl.set("os_run_async", os_run_async_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
     // os_run_interactive
     let cfg_ref_i = cfg_tbl.clone();
     let cwd_str_i = cwd_str.clone();
+    let alt_screen = app.screen_mode.uses_alternate();
     let os_run_interactive_fn = lua
         .create_function(move |_, cmd: String| {
-            #[cfg(windows)]
-            let program = "cmd";
-            #[cfg(windows)]
-            let args: &[&str] = &["/C", &cmd];
-            #[cfg(not(windows))]
-            let program = "sh";
-            #[cfg(not(windows))]
-            let args: &[&str] = &["-lc", &cmd];
-            // leave tui
-            disable_raw_mode().ok();
-            let _ = crossterm::execute!(stdout(), LeaveAlternateScreen);
-            // run
-            let status = std::process::Command::new(program)
-                .args(args)
-                .current_dir(&cwd_str_i)
-                .status();
-            // re-enter tui
-            enable_raw_mode().ok();
-            let _ = crossterm::execute!(stdout(), EnterAlternateScreen);
+            let status = run_interactive(&cmd, &cwd_str_i, alt_screen);
+            let code = status.as_ref().map_or(-1, |s| s.code().unwrap_or(-1));
+            let _ = cfg_ref_i.set("exit_code", code);
             match status
             {
                 Ok(s) if s.success() =>
@@ -466,6 +545,19 @@ fn build_clipboard_helpers(
         .map_err(|e| io::Error::other(e.to_string()))?;
     out.set("delete_selected", delete_selected_fn)
         .map_err(|e| io::Error::other(e.to_string()))?;
+
+    // confirm(question, action): run `action` once the user answers yes
+    let cfg_ref_confirm = cfg_tbl.clone();
+    let confirm_fn = lua
+        .create_function(move |_, (question, action): (String, String)| {
+            let _ = cfg_ref_confirm.set("confirm", "ask");
+            let _ = cfg_ref_confirm.set("confirm_question", question);
+            let _ = cfg_ref_confirm.set("confirm_action", action);
+            Ok(true)
+        })
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    out.set("confirm", confirm_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
     Ok(())
 }
 
@@ -473,8 +565,26 @@ fn build_selection_helpers(
     lua: &Lua,
     out: &Table,
     cfg_tbl: &Table,
+    app: &App,
 ) -> io::Result<()>
 {
+    // get_cwd(): current directory, snapshotted when the action started
+    let cwd = app.cwd.to_string_lossy().to_string();
+    let get_cwd_fn = lua
+        .create_function(move |_, ()| Ok(cwd.clone()))
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    out.set("get_cwd", get_cwd_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    // get_selection(): path of the entry under the cursor, or nil
+    let current =
+        app.selected_entry().map(|e| e.path.to_string_lossy().to_string());
+    let get_selection_fn = lua
+        .create_function(move |_, ()| Ok(current.clone()))
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    out.set("get_selection", get_selection_fn)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
     // select_item(index)
     let cfg_ref = cfg_tbl.clone();
     let select_item_fn = lua
@@ -536,3 +646,44 @@ fn merge_tables(
     }
     Ok(out)
 }
+
+/// Run `cmd` through the platform shell with the terminal handed over: raw
+/// mode (and the alternate screen when `alt_screen`) is left for the duration
+/// of the command and restored afterwards.
+pub(crate) fn run_interactive(
+    cmd: &str,
+    cwd: &str,
+    alt_screen: bool,
+) -> io::Result<std::process::ExitStatus>
+{
+    #[cfg(windows)]
+    let program = "cmd";
+    #[cfg(windows)]
+    let args: &[&str] = &["/C", cmd];
+    #[cfg(not(windows))]
+    let program = "sh";
+    #[cfg(not(windows))]
+    let args: &[&str] = &["-lc", cmd];
+    // leave tui
+    let was_raw = crossterm::terminal::is_raw_mode_enabled().unwrap_or(false);
+    disable_raw_mode().ok();
+    if alt_screen
+    {
+        let _ = crossterm::execute!(stdout(), LeaveAlternateScreen);
+    }
+    // run
+    let status = std::process::Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .status();
+    // re-enter tui
+    if was_raw
+    {
+        enable_raw_mode().ok();
+    }
+    if alt_screen
+    {
+        let _ = crossterm::execute!(stdout(), EnterAlternateScreen);
+    }
+    status
+}
@@ -88,6 +88,18 @@ pub(crate) fn merge_theme_table(
     {
         theme.exec_bg = Some(s);
     }
+    if let Ok(s) = theme_tbl.get::<String>("denied_fg")
+    {
+        theme.denied_fg = Some(s);
+    }
+    if let Ok(s) = theme_tbl.get::<String>("symlink_fg")
+    {
+        theme.symlink_fg = Some(s);
+    }
+    if let Ok(s) = theme_tbl.get::<String>("symlink_broken_fg")
+    {
+        theme.symlink_broken_fg = Some(s);
+    }
     if let Ok(s) = theme_tbl.get::<String>("selection_bar_fg")
     {
         theme.selection_bar_fg = Some(s);
@@ -100,6 +112,61 @@ pub(crate) fn merge_theme_table(
     {
         theme.selection_bar_move_fg = Some(s);
     }
+    if let Ok(s) = theme_tbl.get::<String>("selection_bar_copy_bg")
+    {
+        theme.selection_bar_copy_bg = Some(s);
+    }
+    if let Ok(s) = theme_tbl.get::<String>("selection_bar_move_bg")
+    {
+        theme.selection_bar_move_bg = Some(s);
+    }
+    if let Ok(s) = theme_tbl.get::<String>("clipboard_copy_glyph")
+    {
+        theme.clipboard_copy_glyph = Some(s);
+    }
+    if let Ok(s) = theme_tbl.get::<String>("clipboard_move_glyph")
+    {
+        theme.clipboard_move_glyph = Some(s);
+    }
+    let mods = [
+        ("item_mods", &mut theme.item_mods),
+        ("selected_item_mods", &mut theme.selected_item_mods),
+        ("dir_mods", &mut theme.dir_mods),
+        ("file_mods", &mut theme.file_mods),
+        ("hidden_mods", &mut theme.hidden_mods),
+        ("exec_mods", &mut theme.exec_mods),
+    ];
+    for (key, slot) in mods
+    {
+        if let Some(list) = read_modifier_list(theme_tbl, key)
+        {
+            *slot = Some(list);
+        }
+    }
+}
+
+/// Modifier names under `key`: a list (`{ "bold", "underlined" }`) or a
+/// single string of names separated by spaces, commas or `|`.
+pub(crate) fn read_modifier_list(
+    tbl: &Table,
+    key: &str,
+) -> Option<Vec<String>>
+{
+    match tbl.get::<Value>(key).ok()?
+    {
+        Value::String(s) => Some(
+            s.to_string_lossy()
+                .split(|c: char| c.is_whitespace() || c == ',' || c == '|')
+                .filter(|n| !n.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ),
+        Value::Table(t) =>
+        {
+            Some(t.sequence_values::<String>().flatten().collect())
+        }
+        _ => None,
+    }
 }
 
 pub(crate) fn resolve_theme_path(
@@ -16,10 +16,12 @@ pub struct IconsConfig
 }
 
 #[derive(Debug, Clone, Default)]
-/// Key-handling configuration (currently only sequence timeout).
+/// Key-handling configuration: sequence timeout and which-key group labels.
 pub struct KeysConfig
 {
     pub sequence_timeout_ms: u64,
+    // Prefix (canonical tokens) -> label shown for that which-key group
+    pub groups:              std::collections::HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -30,6 +32,13 @@ pub struct Config
     pub icons:          IconsConfig,
     pub keys:           KeysConfig,
     pub ui:             UiConfig,
+    // Declarative preview commands by lowercased extension; `default` or `*`
+    // is the fallback
+    pub previewers:     std::collections::HashMap<String, String>,
+    // File openers by lowercased extension; `default` is the fallback
+    pub openers:        std::collections::HashMap<String, String>,
+    // Lua action index run before `nav:enter` (`lsv.on_enter`)
+    pub on_enter:       Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -39,6 +48,9 @@ pub struct KeyMapping
     pub sequence:    String,
     pub action:      String,
     pub description: Option<String>,
+    /// Overrides `keys.sequence_timeout_ms` while this mapping's sequence is
+    /// being typed (`0` never expires).
+    pub timeout_ms:  Option<u64>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -54,24 +66,52 @@ pub struct UiPanes
 /// User interface configuration block replicated from Lua.
 pub struct UiConfig
 {
-    pub panes:          Option<UiPanes>,
-    pub show_hidden:    bool,
-    pub max_list_items: usize,
-    pub date_format:    Option<String>,
-    pub header_left:    Option<String>,
-    pub header_right:   Option<String>,
-    pub header_bg:      Option<String>,
-    pub header_fg:      Option<String>,
-    pub row:            Option<UiRowFormat>,
-    pub row_widths:     Option<UiRowWidths>,
-    pub display_mode:   Option<String>,
-    pub sort:           Option<String>,
-    pub sort_reverse:   Option<bool>,
-    pub show:           Option<String>,
-    pub theme_path:     Option<PathBuf>,
-    pub theme:          Option<UiTheme>,
-    pub confirm_delete: bool,
-    pub modals:         Option<UiModals>,
+    pub panes:                 Option<UiPanes>,
+    pub show_hidden:           bool,
+    pub max_list_items:        usize,
+    pub date_format:           Option<String>,
+    pub header_left:           Option<String>,
+    pub header_right:          Option<String>,
+    pub header_bg:             Option<String>,
+    pub header_fg:             Option<String>,
+    pub row:                   Option<UiRowFormat>,
+    pub row_widths:            Option<UiRowWidths>,
+    pub display_mode:          Option<String>,
+    pub sort:                  Option<String>,
+    pub sort_reverse:          Option<bool>,
+    pub show:                  Option<String>,
+    pub theme_path:            Option<PathBuf>,
+    pub theme:                 Option<UiTheme>,
+    // Inline `ui.theme` table, re-applied over the file when it is reloaded
+    pub theme_overlay:         Option<UiTheme>,
+    pub confirm_delete:        bool,
+    pub modals:                Option<UiModals>,
+    pub auto_refresh:          bool,
+    pub dir_sizes:             bool,
+    pub preview_debounce_ms:   u64,
+    pub permissions_octal:     bool,
+    pub syntax_highlight:      bool,
+    pub preview_line_numbers:  bool,
+    pub preview_squeeze_blank: bool,
+    pub preview_wrap:          bool,
+    pub clipboard_tool:        bool,
+    pub command_cwd:           Option<String>,
+    pub border_style:          Option<String>,
+    pub show_spinner:          bool,
+    pub preview_keep_file:     bool,
+    pub dir_slash:             bool,
+    pub scrolloff:             usize,
+    pub note_template:         Option<String>,
+    pub notes_dir:             Option<PathBuf>,
+    pub bulk_report:           bool,
+    pub preview_follows:       Option<String>,
+    pub line_numbers:          Option<String>,
+    pub search_case:           Option<String>,
+    pub dir_preview:           Option<String>,
+    // Absolute path of the selection as the preview pane's title
+    pub show_selection_path:   bool,
+    // Run a project-local `.lsv.lua` (global config only)
+    pub project_config:        bool,
 }
 
 impl Default for UiConfig
@@ -79,24 +119,49 @@ impl Default for UiConfig
     fn default() -> Self
     {
         Self {
-            panes:          None,
-            show_hidden:    false,
-            max_list_items: 5000,
-            date_format:    None,
-            header_left:    None,
-            header_right:   None,
-            header_bg:      None,
-            header_fg:      None,
-            row:            Some(UiRowFormat::default()),
-            row_widths:     None,
-            display_mode:   None,
-            sort:           None,
-            sort_reverse:   None,
-            show:           None,
-            theme_path:     None,
-            theme:          None,
-            confirm_delete: true,
-            modals:         None,
+            panes:                 None,
+            show_hidden:           false,
+            max_list_items:        5000,
+            date_format:           None,
+            header_left:           None,
+            header_right:          None,
+            header_bg:             None,
+            header_fg:             None,
+            row:                   Some(UiRowFormat::default()),
+            row_widths:            None,
+            display_mode:          None,
+            sort:                  None,
+            sort_reverse:          None,
+            show:                  None,
+            theme_path:            None,
+            theme:                 None,
+            theme_overlay:         None,
+            confirm_delete:        true,
+            modals:                None,
+            auto_refresh:          false,
+            dir_sizes:             false,
+            preview_debounce_ms:   0,
+            permissions_octal:     false,
+            syntax_highlight:      false,
+            preview_line_numbers:  false,
+            preview_squeeze_blank: false,
+            preview_wrap:          true,
+            clipboard_tool:        false,
+            command_cwd:           None,
+            border_style:          None,
+            show_spinner:          true,
+            preview_keep_file:     false,
+            dir_slash:             true,
+            scrolloff:             0,
+            note_template:         None,
+            notes_dir:             None,
+            bulk_report:           false,
+            preview_follows:       None,
+            line_numbers:          None,
+            search_case:           None,
+            dir_preview:           None,
+            show_selection_path:   false,
+            project_config:        false,
         }
     }
 }
@@ -170,8 +235,79 @@ pub struct UiTheme
     pub hidden_bg:             Option<String>,
     pub exec_fg:               Option<String>,
     pub exec_bg:               Option<String>,
+    pub denied_fg:             Option<String>,
+    pub symlink_fg:            Option<String>,
+    pub symlink_broken_fg:     Option<String>,
     // Selection indicator (bar) colours
     pub selection_bar_fg:      Option<String>,
     pub selection_bar_copy_fg: Option<String>,
     pub selection_bar_move_fg: Option<String>,
+    pub selection_bar_copy_bg: Option<String>,
+    pub selection_bar_move_bg: Option<String>,
+    // Indicator characters for entries in the clipboard (default `┃`)
+    pub clipboard_copy_glyph:  Option<String>,
+    pub clipboard_move_glyph:  Option<String>,
+    // Text modifiers ("bold", "underlined", ...) per entry kind
+    pub item_mods:             Option<Vec<String>>,
+    pub selected_item_mods:    Option<Vec<String>>,
+    pub dir_mods:              Option<Vec<String>>,
+    pub file_mods:             Option<Vec<String>>,
+    pub hidden_mods:           Option<Vec<String>>,
+    pub exec_mods:             Option<Vec<String>>,
+}
+
+impl UiTheme
+{
+    /// Copy every colour set in `top` over this theme.
+    pub fn overlay(
+        &mut self,
+        top: &UiTheme,
+    )
+    {
+        macro_rules! take {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if top.$field.is_some()
+                    {
+                        self.$field = top.$field.clone();
+                    }
+                )*
+            };
+        }
+        take!(
+            pane_bg,
+            border_fg,
+            item_fg,
+            item_bg,
+            selected_item_fg,
+            selected_item_bg,
+            title_fg,
+            title_bg,
+            info_fg,
+            dir_fg,
+            dir_bg,
+            file_fg,
+            file_bg,
+            hidden_fg,
+            hidden_bg,
+            exec_fg,
+            exec_bg,
+            denied_fg,
+            symlink_fg,
+            symlink_broken_fg,
+            selection_bar_fg,
+            selection_bar_copy_fg,
+            selection_bar_move_fg,
+            selection_bar_copy_bg,
+            selection_bar_move_bg,
+            clipboard_copy_glyph,
+            clipboard_move_glyph,
+            item_mods,
+            selected_item_mods,
+            dir_mods,
+            file_mods,
+            hidden_mods,
+            exec_mods,
+        );
+    }
 }
@@ -0,0 +1,183 @@
+//! Background recursive directory sizes for the `size` info column.
+//!
+//! Sizes are computed on a single worker thread and cached per path together
+//! with the directory mtime observed when the job was queued; a changed mtime
+//! invalidates the cached value.
+
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::mpsc,
+    time::SystemTime,
+};
+
+type Job = (PathBuf, Option<SystemTime>);
+type JobResult = (PathBuf, Option<SystemTime>, u64);
+
+#[derive(Default)]
+pub struct DirSizeCache
+{
+    sizes:   HashMap<PathBuf, (u64, Option<SystemTime>)>,
+    pending: HashSet<PathBuf>,
+    jobs:    Option<mpsc::Sender<Job>>,
+    results: Option<mpsc::Receiver<JobResult>>,
+}
+
+impl DirSizeCache
+{
+    /// Cached size for `path`, if computed for the given directory mtime.
+    pub fn get(
+        &self,
+        path: &Path,
+        mtime: Option<SystemTime>,
+    ) -> Option<u64>
+    {
+        self.sizes.get(path).and_then(|(size, at)| {
+            if *at == mtime { Some(*size) } else { None }
+        })
+    }
+
+    /// True while sizes are still being computed.
+    pub fn is_busy(&self) -> bool
+    {
+        !self.pending.is_empty()
+    }
+
+    fn ensure_worker(&mut self) -> Option<&mpsc::Sender<Job>>
+    {
+        if self.jobs.is_none()
+        {
+            let (job_tx, job_rx) = mpsc::channel::<Job>();
+            let (res_tx, res_rx) = mpsc::channel::<JobResult>();
+            let spawned = std::thread::Builder::new()
+                .name("lsv-dir-sizes".into())
+                .spawn(move || {
+                    while let Ok((path, mtime)) = job_rx.recv()
+                    {
+                        let size = dir_size_recursive(&path);
+                        if res_tx.send((path, mtime, size)).is_err()
+                        {
+                            break;
+                        }
+                    }
+                });
+            if let Err(e) = spawned
+            {
+                crate::trace::log(format!("[dir_sizes] spawn failed: {}", e));
+                return None;
+            }
+            self.jobs = Some(job_tx);
+            self.results = Some(res_rx);
+        }
+        self.jobs.as_ref()
+    }
+
+    /// Move finished results into the cache.
+    fn drain(&mut self)
+    {
+        if let Some(rx) = self.results.as_ref()
+        {
+            while let Ok((path, mtime, size)) = rx.try_recv()
+            {
+                self.pending.remove(&path);
+                self.sizes.insert(path, (size, mtime));
+            }
+        }
+    }
+}
+
+/// Total size in bytes of regular files under `path`. Symlinks are not
+/// followed; unreadable entries are skipped.
+pub fn dir_size_recursive(path: &Path) -> u64
+{
+    dir_size_bounded(path, usize::MAX).0
+}
+
+/// Like [`dir_size_recursive`] but stops after visiting `max_entries`
+/// entries. The flag is true when the walk was cut short, in which case the
+/// size is a lower bound.
+pub fn dir_size_bounded(
+    path: &Path,
+    max_entries: usize,
+) -> (u64, bool)
+{
+    let mut total = 0u64;
+    let mut seen = 0usize;
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop()
+    {
+        let Ok(rd) = fs::read_dir(&dir)
+        else
+        {
+            continue;
+        };
+        for ent in rd.flatten()
+        {
+            if seen >= max_entries
+            {
+                return (total, true);
+            }
+            seen += 1;
+            let Ok(meta) = fs::symlink_metadata(ent.path())
+            else
+            {
+                continue;
+            };
+            if meta.is_dir()
+            {
+                stack.push(ent.path());
+            }
+            else if meta.is_file()
+            {
+                total = total.saturating_add(meta.len());
+            }
+        }
+    }
+    (total, false)
+}
+
+/// Collect finished sizes and queue directories in the current listing whose
+/// size is missing or stale. No-op unless `ui.dir_sizes` is enabled and the
+/// size column is shown.
+pub fn poll(app: &mut crate::app::App)
+{
+    app.dir_sizes.drain();
+    if !app.config.ui.dir_sizes
+        || !matches!(app.info_mode, crate::app::InfoMode::Size)
+    {
+        return;
+    }
+    let wanted: Vec<Job> = app
+        .current_entries
+        .iter()
+        .filter(|e| e.is_dir)
+        .filter(|e| {
+            !app.dir_sizes.pending.contains(&e.path)
+                && app.dir_sizes.get(&e.path, e.mtime).is_none()
+        })
+        .map(|e| (e.path.clone(), e.mtime))
+        .collect();
+    if wanted.is_empty()
+    {
+        return;
+    }
+    let Some(tx) = app.dir_sizes.ensure_worker().cloned()
+    else
+    {
+        return;
+    };
+    for (path, mtime) in wanted
+    {
+        if tx.send((path.clone(), mtime)).is_ok()
+        {
+            app.dir_sizes.pending.insert(path);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Immediate child counts for the `entries` info column.
+//!
+//! Counts are computed on first use while drawing and cached per path
+//! together with the directory mtime and hidden-file setting they were taken
+//! with, so scrolling does not repeat `read_dir` calls.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::SystemTime,
+};
+
+// (directory mtime, show_hidden, count)
+type Count = (Option<SystemTime>, bool, usize);
+
+#[derive(Default)]
+pub struct EntryCountCache
+{
+    counts: RefCell<HashMap<PathBuf, Count>>,
+}
+
+impl EntryCountCache
+{
+    /// Number of files and directories directly inside `path` (dotfiles
+    /// only when `show_hidden`), or `None` when it cannot be read.
+    pub fn get(
+        &self,
+        path: &Path,
+        mtime: Option<SystemTime>,
+        show_hidden: bool,
+    ) -> Option<usize>
+    {
+        if let Some((at, hidden, n)) = self.counts.borrow().get(path)
+            && *at == mtime
+            && *hidden == show_hidden
+        {
+            return Some(*n);
+        }
+        let n = fs::read_dir(path)
+            .ok()?
+            .flatten()
+            .filter(|e| {
+                show_hidden || !e.file_name().to_string_lossy().starts_with('.')
+            })
+            .count();
+        self.counts
+            .borrow_mut()
+            .insert(path.to_path_buf(), (mtime, show_hidden, n));
+        Some(n)
+    }
+}
@@ -0,0 +1,100 @@
+//! Name filters that narrow the current listing (`filter:glob`,
+//! `filter:regex`).
+//!
+//! Globs are translated to an anchored regex: `*` matches any run of
+//! characters, `?` a single character and `[...]` a character class (`[!...]`
+//! negates). Both kinds match the entry name only; letter case follows
+//! `ui.search_case` (see `CaseMode`).
+
+use fancy_regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterKind
+{
+    Glob,
+    Regex,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListFilter
+{
+    pub kind:    FilterKind,
+    pub pattern: String,
+    re:          Regex,
+}
+
+impl ListFilter
+{
+    /// Compile `pattern`; the error describes why it is invalid.
+    pub fn new(
+        kind: FilterKind,
+        pattern: &str,
+        case_sensitive: bool,
+    ) -> Result<Self, String>
+    {
+        let mut source = match kind
+        {
+            FilterKind::Glob => glob_to_regex(pattern)?,
+            FilterKind::Regex => pattern.to_string(),
+        };
+        if !case_sensitive
+        {
+            source.insert_str(0, "(?i)");
+        }
+        let re = Regex::new(&source).map_err(|e| e.to_string())?;
+        Ok(Self { kind, pattern: pattern.to_string(), re })
+    }
+
+    pub fn matches(
+        &self,
+        name: &str,
+    ) -> bool
+    {
+        self.re.is_match(name).unwrap_or(false)
+    }
+}
+
+fn glob_to_regex(glob: &str) -> Result<String, String>
+{
+    let mut out = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next()
+    {
+        match c
+        {
+            '*' => out.push_str(".*"),
+            '?' => out.push('.'),
+            '[' =>
+            {
+                out.push('[');
+                if chars.peek() == Some(&'!')
+                {
+                    chars.next();
+                    out.push('^');
+                }
+                let mut closed = false;
+                for c in chars.by_ref()
+                {
+                    if c == ']'
+                    {
+                        closed = true;
+                        break;
+                    }
+                    if c == '\\' || c == '['
+                    {
+                        out.push('\\');
+                    }
+                    out.push(c);
+                }
+                if !closed
+                {
+                    return Err(format!("unclosed '[' in {}", glob));
+                }
+                out.push(']');
+            }
+            _ => out.push_str(&fancy_regex::escape(&c.to_string())),
+        }
+    }
+    out.push('$');
+    Ok(out)
+}
@@ -4,23 +4,46 @@ use std::{
 };
 
 /// Recursively copy a file or directory tree from `src` to `dst`.
+///
+/// Directory symlinks are followed, but a link back to a directory that is
+/// already being copied fails with an error instead of recursing forever.
 pub fn copy_path_recursive(
     src: &Path,
     dst: &Path,
 ) -> io::Result<()>
+{
+    copy_tree(src, dst, &mut Vec::new())
+}
+
+fn copy_tree(
+    src: &Path,
+    dst: &Path,
+    ancestors: &mut Vec<std::path::PathBuf>,
+) -> io::Result<()>
 {
     let meta = std::fs::metadata(src)?;
     if meta.is_dir()
     {
+        let canon = std::fs::canonicalize(src)?;
+        if ancestors.contains(&canon)
+        {
+            return Err(io::Error::other(format!(
+                "symlink loop: {} leads back to {}",
+                src.display(),
+                canon.display()
+            )));
+        }
         std::fs::create_dir_all(dst)?;
+        ancestors.push(canon);
         for entry in std::fs::read_dir(src)?
         {
             let de = entry?;
             let p = de.path();
             let name = de.file_name();
             let target = dst.join(name);
-            copy_path_recursive(&p, &target)?;
+            copy_tree(&p, &target, ancestors)?;
         }
+        ancestors.pop();
         Ok(())
     }
     else
@@ -54,6 +77,60 @@ pub fn move_path_with_fallback(
     }
 }
 
+/// First path beside `path` named with a ` (n)` suffix before the extension
+/// (`report.txt` -> `report (1).txt`, `dir` -> `dir (1)`) that does not exist.
+pub fn next_free_copy_name(path: &Path) -> std::path::PathBuf
+{
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let lossy = |s: Option<&std::ffi::OsStr>| {
+        s.map(|s| s.to_string_lossy().to_string()).unwrap_or_default()
+    };
+    let (stem, ext) = match path.extension()
+    {
+        Some(e) if !path.is_dir() =>
+        {
+            (lossy(path.file_stem()), format!(".{}", e.to_string_lossy()))
+        }
+        _ => (lossy(path.file_name()), String::new()),
+    };
+    (1..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, ext)))
+        .find(|p| std::fs::symlink_metadata(p).is_err())
+        .expect("unbounded suffix search")
+}
+
+/// Ensure the note named by formatting the chrono `template` with `now`
+/// exists under `dir`, creating it (and any missing parent directories)
+/// when absent. Returns the note path and whether it was just created.
+pub fn ensure_note(
+    dir: &Path,
+    template: &str,
+    now: chrono::DateTime<chrono::Local>,
+) -> io::Result<(std::path::PathBuf, bool)>
+{
+    use std::fmt::Write;
+    let mut name = String::new();
+    if write!(name, "{}", now.format(template)).is_err()
+        || name.trim().is_empty()
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid note template '{}'", template),
+        ));
+    }
+    let path = dir.join(name);
+    if let Some(parent) = path.parent()
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    match std::fs::OpenOptions::new().write(true).create_new(true).open(&path)
+    {
+        Ok(_) => Ok((path, true)),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok((path, false)),
+        Err(e) => Err(e),
+    }
+}
+
 /// Remove a path (file or directory recursively).
 pub fn remove_path_all(path: &Path) -> io::Result<()>
 {
@@ -66,3 +143,36 @@ pub fn remove_path_all(path: &Path) -> io::Result<()>
         std::fs::remove_file(path)
     }
 }
+
+/// Set the access and modification times of `path` to now, creating an empty
+/// file when nothing exists there. Returns true when the file was created.
+pub fn touch_path(path: &Path) -> std::io::Result<bool>
+{
+    let now = std::time::SystemTime::now();
+    let times = std::fs::FileTimes::new().set_accessed(now).set_modified(now);
+    match std::fs::metadata(path)
+    {
+        Ok(meta) if meta.is_dir() =>
+        {
+            std::fs::File::open(path)?.set_times(times)?;
+            Ok(false)
+        }
+        Ok(_) =>
+        {
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(path)?
+                .set_times(times)?;
+            Ok(false)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound =>
+        {
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(path)?;
+            Ok(true)
+        }
+        Err(e) => Err(e),
+    }
+}
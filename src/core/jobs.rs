@@ -0,0 +1,152 @@
+//! Shell commands run off the UI thread for `lsv.os_run_async`.
+//!
+//! Each command runs on its own worker thread with the same shell and
+//! output capture as `lsv.os_run`; results come back over one channel that
+//! [`poll`] drains from the event loop.
+
+use std::{
+    io,
+    path::PathBuf,
+    process::{
+        Command,
+        Output,
+    },
+    sync::mpsc,
+};
+
+type JobResult = (String, io::Result<Output>);
+
+#[derive(Default)]
+pub struct Jobs
+{
+    running: usize,
+    tx:      Option<mpsc::Sender<JobResult>>,
+    rx:      Option<mpsc::Receiver<JobResult>>,
+}
+
+impl Jobs
+{
+    /// True while any command is still running.
+    pub fn is_busy(&self) -> bool
+    {
+        self.running > 0
+    }
+
+    /// Number of commands still running.
+    pub fn running(&self) -> usize
+    {
+        self.running
+    }
+
+    /// Start `cmd` in `cwd` on a worker thread.
+    pub fn spawn(
+        &mut self,
+        cmd: &str,
+        cwd: PathBuf,
+    ) -> io::Result<()>
+    {
+        if self.tx.is_none()
+        {
+            let (tx, rx) = mpsc::channel::<JobResult>();
+            self.tx = Some(tx);
+            self.rx = Some(rx);
+        }
+        let Some(tx) = self.tx.clone()
+        else
+        {
+            return Ok(());
+        };
+        let cmd = cmd.to_string();
+        std::thread::Builder::new().name("lsv-os-run".into()).spawn(
+            move || {
+                let out = shell_command(&cmd).current_dir(&cwd).output();
+                let _ = tx.send((cmd, out));
+            },
+        )?;
+        self.running += 1;
+        Ok(())
+    }
+
+    fn drain(&mut self) -> Vec<JobResult>
+    {
+        let done: Vec<JobResult> = match self.rx.as_ref()
+        {
+            Some(rx) => rx.try_iter().collect(),
+            None => Vec::new(),
+        };
+        self.running = self.running.saturating_sub(done.len());
+        done
+    }
+}
+
+/// `cmd` wrapped for the system shell (`sh -lc`, or `cmd /C` on Windows).
+pub(crate) fn shell_command(cmd: &str) -> Command
+{
+    #[cfg(windows)]
+    {
+        let mut c = Command::new("cmd");
+        c.arg("/C").arg(cmd);
+        c
+    }
+    #[cfg(not(windows))]
+    {
+        let mut c = Command::new("sh");
+        c.arg("-lc").arg(cmd);
+        c
+    }
+}
+
+/// Captured stdout, followed by stderr on a new line when there is any.
+pub(crate) fn combined_output(output: &Output) -> Vec<u8>
+{
+    let mut buf = output.stdout.clone();
+    if !output.stderr.is_empty()
+    {
+        buf.push(b'\n');
+        buf.extend_from_slice(&output.stderr);
+    }
+    buf
+}
+
+/// Report finished commands: record the exit status, show any output (or a
+/// failure) in the Output overlay like `os_run`, and add a completion
+/// message. The overlay is not opened over a prompt or confirmation.
+pub fn poll(app: &mut crate::app::App)
+{
+    for (cmd, out) in app.jobs.drain()
+    {
+        let title = format!("$ {}", cmd);
+        let (code, text) = match out
+        {
+            Ok(output) =>
+            {
+                let buf = combined_output(&output);
+                let code = output.status.code().unwrap_or(-1);
+                crate::trace::log(format!(
+                    "[os_run_async] exit={:?} bytes_out={}",
+                    output.status.code(),
+                    buf.len()
+                ));
+                let text = (!buf.is_empty() || !output.status.success())
+                    .then(|| String::from_utf8_lossy(&buf).to_string());
+                (code, text)
+            }
+            Err(e) =>
+            {
+                crate::trace::log(format!("[os_run_async] error: {}", e));
+                (-1, Some(format!("<error: {}>", e)))
+            }
+        };
+        app.last_exit = Some(code);
+        if let Some(text) = text
+            && matches!(
+                app.overlay,
+                crate::app::Overlay::None | crate::app::Overlay::Output { .. }
+            )
+        {
+            app.display_output(&title, &text);
+        }
+        app.add_message(&format!("Finished (exit {}): {}", code, title));
+        app.force_full_redraw = true;
+    }
+}
@@ -1,10 +1,31 @@
 use std::{
     io,
-    path::Path,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::mpsc,
 };
 
 use crate::actions::internal::SortKey;
 
+/// Entries read on the UI thread before the rest of a large directory is
+/// handed to a background reader.
+pub const FIRST_PAGE: usize = 1000;
+/// Entries per page sent by the background reader.
+const PAGE: usize = 5000;
+/// Directories read at most by one flat-view walk.
+pub const FLAT_MAX_DIRS: usize = 10_000;
+
+/// Count the hidden entries (dotfiles) directly inside `path`.
+pub fn count_hidden(path: &Path) -> io::Result<usize>
+{
+    Ok(std::fs::read_dir(path)?
+        .filter_map(|res| res.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with('.'))
+        .count())
+}
+
 /// Read a directory and return entries sorted per key and direction.
 /// Hidden files (dotfiles) are filtered when `show_hidden` is false.
 pub fn read_dir_sorted(
@@ -16,132 +37,439 @@ pub fn read_dir_sorted(
     max_items: usize,
 ) -> io::Result<Vec<crate::app::DirEntryInfo>>
 {
-    use std::fs;
-    let mut entries: Vec<crate::app::DirEntryInfo> = fs::read_dir(path)?
+    let mut entries: Vec<crate::app::DirEntryInfo> = std::fs::read_dir(path)?
         .filter_map(|res| res.ok())
-        .filter_map(|e| {
-            let path = e.path();
-            let name = e.file_name().to_string_lossy().to_string();
-            if !show_hidden && name.starts_with('.')
-            {
-                return None;
-            }
-            match e.file_type()
+        .filter_map(|e| entry_info(&e, show_hidden, need_meta))
+        .take(max_items)
+        .collect();
+
+    entries.sort_by(|a, b| compare_entries(a, b, sort_key, sort_reverse));
+    Ok(entries)
+}
+
+pub(crate) fn entry_info(
+    e: &std::fs::DirEntry,
+    show_hidden: bool,
+    need_meta: bool,
+) -> Option<crate::app::DirEntryInfo>
+{
+    let path = e.path();
+    let name = e.file_name().to_string_lossy().to_string();
+    if !show_hidden && name.starts_with('.')
+    {
+        return None;
+    }
+    // Keep entries even when their type or metadata cannot be read
+    // (permission denied, broken symlinks); they are flagged as
+    // unreadable instead of vanishing from the listing.
+    let ft = e.file_type().ok();
+    let is_symlink = ft.is_some_and(|t| t.is_symlink());
+    let check = need_meta || ft.is_none() || is_symlink;
+    let meta = if check { std::fs::metadata(&path).ok() } else { None };
+    // Links group with directories when they resolve to one
+    let is_dir = match ft
+    {
+        Some(t) if !t.is_symlink() => t.is_dir(),
+        _ => meta.as_ref().is_some_and(|m| m.is_dir()),
+    };
+    let link_target =
+        if is_symlink { std::fs::read_link(&path).ok() } else { None };
+    Some(crate::app::DirEntryInfo {
+        name,
+        path,
+        is_dir,
+        is_symlink,
+        link_target,
+        size: meta.as_ref().map(|m| m.len()).unwrap_or(0),
+        mtime: meta.as_ref().and_then(|m| m.modified().ok()),
+        ctime: meta.as_ref().and_then(|m| m.created().ok()),
+        atime: meta.as_ref().and_then(|m| m.accessed().ok()),
+        unreadable: check && meta.is_none(),
+    })
+}
+
+/// The remainder of a large directory being read on a background thread.
+/// Pages arrive already sorted; dropping the stream stops the reader.
+pub struct ListingStream
+{
+    path:         PathBuf,
+    sort_key:     SortKey,
+    sort_reverse: bool,
+    pages:        mpsc::Receiver<Vec<crate::app::DirEntryInfo>>,
+    // Entry to select once its page arrives, and the entry selected in the
+    // meantime (the wish is dropped when the user moves elsewhere)
+    wanted:       Option<(String, Option<String>)>,
+}
+
+impl ListingStream
+{
+    pub fn path(&self) -> &Path
+    {
+        &self.path
+    }
+
+    /// Select `name` when a later page brings it in, unless the selection
+    /// has moved away from `fallback` by then.
+    pub fn want(
+        &mut self,
+        name: &str,
+        fallback: Option<String>,
+    )
+    {
+        self.wanted = Some((name.to_string(), fallback));
+    }
+
+    /// Merge every page received so far into the sorted `entries`. Returns
+    /// whether anything was added and whether the reader has finished.
+    pub fn drain_into(
+        &self,
+        entries: &mut Vec<crate::app::DirEntryInfo>,
+    ) -> (bool, bool)
+    {
+        let mut added = false;
+        loop
+        {
+            match self.pages.try_recv()
             {
-                Ok(ft) =>
+                Ok(page) =>
                 {
-                    if need_meta && !matches!(sort_key, SortKey::Name)
-                    {
-                        // Sorting by size/mtime/ctime requires metadata for
-                        // accuracy
-                        let meta = fs::metadata(&path).ok();
-                        let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
-                        let mtime =
-                            meta.as_ref().and_then(|m| m.modified().ok());
-                        let ctime =
-                            meta.as_ref().and_then(|m| m.created().ok());
-                        Some(crate::app::DirEntryInfo {
-                            name,
-                            path,
-                            is_dir: ft.is_dir(),
-                            size,
-                            mtime,
-                            ctime,
-                        })
-                    }
-                    else if need_meta
-                    {
-                        // Name sort but meta requested for UI info; fetch once
-                        let meta = fs::metadata(&path).ok();
-                        let size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
-                        let mtime =
-                            meta.as_ref().and_then(|m| m.modified().ok());
-                        let ctime =
-                            meta.as_ref().and_then(|m| m.created().ok());
-                        Some(crate::app::DirEntryInfo {
-                            name,
-                            path,
-                            is_dir: ft.is_dir(),
-                            size,
-                            mtime,
-                            ctime,
-                        })
-                    }
-                    else
-                    {
-                        // Fast path: avoid metadata when not needed
-                        Some(crate::app::DirEntryInfo {
-                            name,
-                            path,
-                            is_dir: ft.is_dir(),
-                            size: 0,
-                            mtime: None,
-                            ctime: None,
-                        })
-                    }
+                    added |= !page.is_empty();
+                    merge_sorted(
+                        entries,
+                        page,
+                        self.sort_key,
+                        self.sort_reverse,
+                    );
                 }
-                Err(_) => None,
+                Err(mpsc::TryRecvError::Empty) => return (added, false),
+                Err(mpsc::TryRecvError::Disconnected) => return (added, true),
             }
-        })
-        .take(max_items)
-        .collect();
+        }
+    }
+}
 
-    entries.sort_by(|a, b| {
-        // Always keep directories before files
-        match (a.is_dir, b.is_dir)
+/// Like [`read_dir_sorted`], but only the first `first_page` entries are read
+/// before returning. When the directory has more, the rest (up to
+/// `max_items` in total) is read on a background thread and delivered
+/// through the returned [`ListingStream`].
+pub fn read_dir_streaming(
+    path: &Path,
+    show_hidden: bool,
+    sort_key: SortKey,
+    sort_reverse: bool,
+    need_meta: bool,
+    max_items: usize,
+    first_page: usize,
+) -> io::Result<(Vec<crate::app::DirEntryInfo>, Option<ListingStream>)>
+{
+    let mut rd = std::fs::read_dir(path)?;
+    let mut entries = Vec::new();
+    let limit = first_page.min(max_items);
+    while entries.len() < limit
+    {
+        match rd.next()
         {
-            (true, false) => return std::cmp::Ordering::Less,
-            (false, true) => return std::cmp::Ordering::Greater,
-            _ =>
+            Some(Ok(e)) =>
+            {
+                entries.extend(entry_info(&e, show_hidden, need_meta));
+            }
+            Some(Err(_)) =>
             {}
+            None => break,
         }
-        let ord = match sort_key
-        {
-            SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-            SortKey::Size =>
+    }
+    // Stopping early at the first page (rather than the cap) means more
+    // entries may follow
+    let more = entries.len() >= limit && limit < max_items;
+    entries.sort_by(|a, b| compare_entries(a, b, sort_key, sort_reverse));
+    if !more
+    {
+        return Ok((entries, None));
+    }
+
+    let remaining = max_items - entries.len();
+    let (tx, rx) = mpsc::channel();
+    let spawned = std::thread::Builder::new().name("lsv-listing".into()).spawn(
+        move || {
+            let mut rest = rd
+                .filter_map(|res| res.ok())
+                .filter_map(|e| entry_info(&e, show_hidden, need_meta))
+                .take(remaining)
+                .peekable();
+            while rest.peek().is_some()
             {
-                // When sorting by size, keep directories ordered by name
-                // instead of their (often meaningless)
-                // filesystem size.
-                if a.is_dir && b.is_dir
-                {
-                    a.name.to_lowercase().cmp(&b.name.to_lowercase())
-                }
-                else
+                let mut page: Vec<_> = rest.by_ref().take(PAGE).collect();
+                page.sort_by(|a, b| {
+                    compare_entries(a, b, sort_key, sort_reverse)
+                });
+                if tx.send(page).is_err()
                 {
-                    a.size.cmp(&b.size)
+                    break;
                 }
             }
-            SortKey::MTime =>
-            {
-                let at = a.mtime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                let bt = b.mtime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                at.cmp(&bt)
-            }
-            SortKey::CTime =>
+        },
+    );
+    if let Err(e) = spawned
+    {
+        crate::trace::log(format!("[listing] spawn failed: {}", e));
+        return read_dir_sorted(
+            path,
+            show_hidden,
+            sort_key,
+            sort_reverse,
+            need_meta,
+            max_items,
+        )
+        .map(|v| (v, None));
+    }
+    let stream = ListingStream {
+        path: path.to_path_buf(),
+        sort_key,
+        sort_reverse,
+        pages: rx,
+        wanted: None,
+    };
+    Ok((entries, Some(stream)))
+}
+
+/// Merge the sorted `page` into the sorted `entries`, keeping the order.
+fn merge_sorted(
+    entries: &mut Vec<crate::app::DirEntryInfo>,
+    page: Vec<crate::app::DirEntryInfo>,
+    sort_key: SortKey,
+    sort_reverse: bool,
+)
+{
+    if page.is_empty()
+    {
+        return;
+    }
+    let old = std::mem::take(entries);
+    entries.reserve(old.len() + page.len());
+    let mut a = old.into_iter().peekable();
+    let mut b = page.into_iter().peekable();
+    loop
+    {
+        let take_a = match (a.peek(), b.peek())
+        {
+            (Some(x), Some(y)) =>
             {
-                let at = a.ctime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                let bt = b.ctime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                at.cmp(&bt)
+                compare_entries(x, y, sort_key, sort_reverse)
+                    != std::cmp::Ordering::Greater
             }
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => break,
         };
-        if sort_reverse
+        let next = if take_a { a.next() } else { b.next() };
+        entries.extend(next);
+    }
+}
+
+/// Merge pages of a streaming listing into the current entries, keeping the
+/// selected entry (and the visual anchor) on the same names while the list
+/// grows, or selecting the entry that was wanted before its page arrived.
+/// Returns true when the listing changed.
+pub fn poll(app: &mut crate::app::App) -> bool
+{
+    let Some(mut stream) = app.listing_stream.take()
+    else
+    {
+        return false;
+    };
+    if stream.path() != app.cwd
+    {
+        return false;
+    }
+    let current_name = app.selected_entry().map(|e| e.name.clone());
+    if stream.wanted.as_ref().is_some_and(|(_, fb)| *fb != current_name)
+    {
+        stream.wanted = None;
+    }
+    let anchor_name = app
+        .visual_anchor
+        .and_then(|i| app.current_entries.get(i))
+        .map(|e| e.name.clone());
+    let (added, done) = stream.drain_into(&mut app.current_entries);
+    if let Some(filter) = app.list_filter.as_ref()
+    {
+        app.current_entries.retain(|e| filter.matches(&e.name));
+    }
+    if !added
+    {
+        if !done
+        {
+            app.listing_stream = Some(stream);
+        }
+        return false;
+    }
+    let position = |app: &crate::app::App, name: &str| -> Option<usize> {
+        app.current_entries.iter().position(|e| e.name == name)
+    };
+    let wanted_idx =
+        stream.wanted.as_ref().and_then(|(name, _)| position(app, name));
+    if let Some(idx) = wanted_idx
+    {
+        stream.wanted = None;
+        app.list_state.select(Some(idx));
+    }
+    else
+    {
+        match current_name.as_deref()
+        {
+            Some(name) => crate::core::selection::reselect_by_name(app, name),
+            None => app.list_state.select(Some(0)),
+        }
+        // Still waiting: the fallback is whatever is selected now
+        if let Some((_, fb)) = stream.wanted.as_mut()
+        {
+            *fb = current_name;
+        }
+    }
+    if app.visual_anchor.is_some()
+    {
+        app.visual_anchor =
+            anchor_name.as_deref().and_then(|name| position(app, name));
+    }
+    if !done
+    {
+        app.listing_stream = Some(stream);
+    }
+    true
+}
+
+/// Ordering used for listings: directories first, then by `sort_key`
+/// (optionally reversed).
+fn compare_entries(
+    a: &crate::app::DirEntryInfo,
+    b: &crate::app::DirEntryInfo,
+    sort_key: SortKey,
+    sort_reverse: bool,
+) -> std::cmp::Ordering
+{
+    // Always keep directories before files
+    match (a.is_dir, b.is_dir)
+    {
+        (true, false) => return std::cmp::Ordering::Less,
+        (false, true) => return std::cmp::Ordering::Greater,
+        _ =>
+        {}
+    }
+    let ord = match sort_key
+    {
+        SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        SortKey::Size =>
         {
-            // For size sort, keep directories ordered by name even when
-            // reversed.
-            if matches!(sort_key, SortKey::Size) && a.is_dir && b.is_dir
+            // When sorting by size, keep directories ordered by name
+            // instead of their (often meaningless)
+            // filesystem size.
+            if a.is_dir && b.is_dir
             {
-                ord
+                a.name.to_lowercase().cmp(&b.name.to_lowercase())
             }
             else
             {
-                ord.reverse()
+                a.size.cmp(&b.size)
             }
         }
-        else
+        SortKey::MTime =>
+        {
+            let at = a.mtime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            let bt = b.mtime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            at.cmp(&bt)
+        }
+        SortKey::CTime =>
+        {
+            let at = a.ctime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            let bt = b.ctime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            at.cmp(&bt)
+        }
+        SortKey::ATime =>
+        {
+            let at = a.atime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            let bt = b.atime.unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            at.cmp(&bt)
+        }
+    };
+    if sort_reverse
+    {
+        // For size sort, keep directories ordered by name even when
+        // reversed.
+        if matches!(sort_key, SortKey::Size) && a.is_dir && b.is_dir
         {
             ord
         }
-    });
-    Ok(entries)
+        else
+        {
+            ord.reverse()
+        }
+    }
+    else
+    {
+        ord
+    }
+}
+
+/// Recursively list the files under `root` for the flat view. Entry names are
+/// paths relative to `root`; directories are descended into but not listed
+/// and symlinked directories are not followed. The walk stops once
+/// `max_items` files are collected or [`FLAT_MAX_DIRS`] directories have been
+/// read, whichever comes first.
+pub fn read_dir_flat(
+    root: &Path,
+    show_hidden: bool,
+    sort_key: SortKey,
+    sort_reverse: bool,
+    need_meta: bool,
+    max_items: usize,
+) -> io::Result<Vec<crate::app::DirEntryInfo>>
+{
+    let mut out = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    let mut first = true;
+    let mut visited = 0usize;
+    while let Some(dir) = stack.pop()
+    {
+        if out.len() >= max_items || visited >= FLAT_MAX_DIRS
+        {
+            break;
+        }
+        visited += 1;
+        let listed = read_dir_sorted(
+            &dir,
+            show_hidden,
+            SortKey::Name,
+            false,
+            need_meta,
+            usize::MAX,
+        );
+        // Only an unreadable root is an error; skip unreadable subtrees
+        let entries = match listed
+        {
+            Ok(v) => v,
+            Err(e) if first => return Err(e),
+            Err(_) => continue,
+        };
+        first = false;
+        for mut e in entries
+        {
+            // Links to directories are listed like files, never followed
+            if e.is_dir && !e.is_symlink
+            {
+                stack.push(e.path);
+                continue;
+            }
+            if out.len() >= max_items
+            {
+                break;
+            }
+            if let Ok(rel) = e.path.strip_prefix(root)
+            {
+                e.name = rel.to_string_lossy().to_string();
+            }
+            out.push(e);
+        }
+    }
+    out.sort_by(|a, b| compare_entries(a, b, sort_key, sort_reverse));
+    Ok(out)
 }
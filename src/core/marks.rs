@@ -63,3 +63,23 @@ pub fn save_marks(
     fs::rename(tmp, path)?;
     Ok(())
 }
+
+/// Read the recorded startup directory (a single absolute path), if any.
+pub fn load_start_dir(path: &Path) -> Option<PathBuf>
+{
+    let text = fs::read_to_string(path).ok()?;
+    let line = text.lines().next()?.trim();
+    if line.is_empty() { None } else { Some(PathBuf::from(line)) }
+}
+
+pub fn save_start_dir(
+    path: &Path,
+    dir: &Path,
+) -> io::Result<()>
+{
+    if let Some(parent) = path.parent()
+    {
+        let _ = fs::create_dir_all(parent);
+    }
+    fs::write(path, format!("{}\n", dir.display()))
+}
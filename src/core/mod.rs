@@ -1,5 +1,13 @@
+pub mod dir_sizes;
+pub mod entry_counts;
+pub mod filter;
 pub mod fs_ops;
+pub mod jobs;
 pub mod listing;
 pub mod marks;
+pub mod os_clipboard;
 pub mod overlays;
 pub mod selection;
+pub mod selection_size;
+pub mod tree;
+pub mod watch;
@@ -0,0 +1,110 @@
+//! Copying text to the system clipboard (distinct from the internal file
+//! clipboard used by copy/move/paste).
+//!
+//! The primary mechanism is an OSC 52 escape sequence written to the
+//! terminal, which also works over SSH. When enabled, a local clipboard tool
+//! (`pbcopy`, `wl-copy`, `xclip`, `clip.exe`) is tried as well.
+
+use std::io::{
+    self,
+    Write,
+};
+
+const B64: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(data: &[u8]) -> String
+{
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3)
+    {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        out.push(B64[(n >> 18) as usize & 63] as char);
+        out.push(B64[(n >> 12) as usize & 63] as char);
+        out.push(
+            if chunk.len() > 1
+            {
+                B64[(n >> 6) as usize & 63] as char
+            }
+            else
+            {
+                '='
+            },
+        );
+        out.push(
+            if chunk.len() > 2 { B64[n as usize & 63] as char } else { '=' },
+        );
+    }
+    out
+}
+
+/// OSC 52 sequence that sets the terminal's clipboard selection to `text`.
+pub fn osc52_sequence(text: &str) -> String
+{
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// Copy `text` to the system clipboard via OSC 52 and, when `use_tool` is
+/// set, also through the first local clipboard tool that succeeds.
+pub fn copy_text(
+    text: &str,
+    use_tool: bool,
+) -> io::Result<()>
+{
+    let mut out = io::stdout();
+    out.write_all(osc52_sequence(text).as_bytes())?;
+    out.flush()?;
+    if use_tool && !copy_with_tool(text)
+    {
+        crate::trace::log("[clipboard] no clipboard tool succeeded");
+    }
+    Ok(())
+}
+
+fn copy_with_tool(text: &str) -> bool
+{
+    use std::process::{
+        Command,
+        Stdio,
+    };
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos")
+    {
+        &[("pbcopy", &[])]
+    }
+    else if cfg!(windows)
+    {
+        &[("clip.exe", &[])]
+    }
+    else
+    {
+        &[("wl-copy", &[]), ("xclip", &["-selection", "clipboard"])]
+    };
+    for (program, args) in candidates
+    {
+        let child = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let Ok(mut child) = child
+        else
+        {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take()
+        {
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        if child.wait().map(|s| s.success()).unwrap_or(false)
+        {
+            return true;
+        }
+    }
+    false
+}
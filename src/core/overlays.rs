@@ -16,6 +16,7 @@ pub fn apply_theme_entry(
 {
     app.config.ui.theme = Some(entry.theme);
     app.config.ui.theme_path = Some(entry.path);
+    app.config.ui.theme_overlay = None;
     app.force_full_redraw = true;
 }
 
@@ -59,6 +60,47 @@ pub fn confirm_theme_picker(app: &mut App)
     app.force_full_redraw = true;
 }
 
+pub fn template_picker_move(
+    app: &mut App,
+    delta: isize,
+)
+{
+    if let Overlay::TemplatePicker(ref mut s) = app.overlay
+        && !s.entries.is_empty()
+    {
+        let last = s.entries.len() as isize - 1;
+        s.selected = (s.selected as isize + delta).clamp(0, last) as usize;
+    }
+}
+
+/// Ask for the destination name of the chosen template, starting from the
+/// template's own name.
+pub fn confirm_template_picker(app: &mut App)
+{
+    let template = match app.overlay
+    {
+        Overlay::TemplatePicker(ref s) => s.entries.get(s.selected).cloned(),
+        _ => None,
+    };
+    let Some(template) = template
+    else
+    {
+        app.overlay = Overlay::None;
+        return;
+    };
+    let input = template
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    app.overlay = Overlay::Prompt(Box::new(PromptState {
+        title: "New file name:".to_string(),
+        cursor: input.len(),
+        input,
+        kind: PromptKind::NewFromTemplate(template),
+    }));
+    app.force_full_redraw = true;
+}
+
 pub fn open_add_entry_prompt(app: &mut App)
 {
     app.overlay = Overlay::Prompt(Box::new(PromptState {
@@ -81,6 +123,77 @@ pub fn open_mark_add_prompt(app: &mut App)
     app.force_full_redraw = true;
 }
 
+pub fn open_goto_path_prompt(app: &mut App)
+{
+    app.overlay = Overlay::Prompt(Box::new(PromptState {
+        title:  "Go to path (Tab completes):".to_string(),
+        input:  String::new(),
+        cursor: 0,
+        kind:   PromptKind::GotoPath,
+    }));
+    app.force_full_redraw = true;
+}
+
+pub fn open_goto_line_prompt(app: &mut App)
+{
+    app.overlay = Overlay::Prompt(Box::new(PromptState {
+        title:  "Go to line:".to_string(),
+        input:  String::new(),
+        cursor: 0,
+        kind:   PromptKind::GotoLine,
+    }));
+    app.force_full_redraw = true;
+}
+
+pub fn open_touch_prompt(app: &mut App)
+{
+    app.overlay = Overlay::Prompt(Box::new(PromptState {
+        title:  "Touch (create or update):".to_string(),
+        input:  String::new(),
+        cursor: 0,
+        kind:   PromptKind::Touch,
+    }));
+    app.force_full_redraw = true;
+}
+
+pub fn open_transfer_to_prompt(
+    app: &mut App,
+    op: crate::app::ClipboardOp,
+)
+{
+    let title = match op
+    {
+        crate::app::ClipboardOp::Copy => "Copy to directory (Tab completes):",
+        crate::app::ClipboardOp::Move => "Move to directory (Tab completes):",
+    };
+    app.overlay = Overlay::Prompt(Box::new(PromptState {
+        title:  title.to_string(),
+        input:  String::new(),
+        cursor: 0,
+        kind:   PromptKind::TransferTo(op),
+    }));
+    app.force_full_redraw = true;
+}
+
+pub fn open_filter_prompt(
+    app: &mut App,
+    kind: crate::core::filter::FilterKind,
+)
+{
+    let title = match kind
+    {
+        crate::core::filter::FilterKind::Glob => "Filter (glob):",
+        crate::core::filter::FilterKind::Regex => "Filter (regex):",
+    };
+    app.overlay = Overlay::Prompt(Box::new(PromptState {
+        title:  title.to_string(),
+        input:  String::new(),
+        cursor: 0,
+        kind:   PromptKind::Filter(kind),
+    }));
+    app.force_full_redraw = true;
+}
+
 pub fn open_rename_entry_prompt(app: &mut App)
 {
     if !app.selected.is_empty()
@@ -137,6 +250,24 @@ pub fn open_rename_entry_prompt(app: &mut App)
     app.force_full_redraw = true;
 }
 
+/// Ask `question`; answering yes dispatches `action`, which may be an action
+/// string or a mapped key sequence (so it can name another Lua action).
+pub fn request_confirm_action(
+    app: &mut App,
+    question: String,
+    action: String,
+)
+{
+    crate::trace::log(format!("[confirm] ask action='{}'", action));
+    app.overlay = Overlay::Confirm(Box::new(ConfirmState {
+        title: "Confirm".to_string(),
+        question,
+        default_yes: false,
+        kind: ConfirmKind::RunAction(action),
+    }));
+    app.force_full_redraw = true;
+}
+
 pub fn request_delete_selected(app: &mut App)
 {
     crate::trace::log("[delete] request_delete_selected()");
@@ -170,9 +301,6 @@ pub fn request_delete_selected(app: &mut App)
     }
     else
     {
-        for p in app.selected.clone().into_iter()
-        {
-            app.perform_delete_path(&p);
-        }
+        app.perform_delete_paths(&items);
     }
 }
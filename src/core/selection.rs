@@ -1,4 +1,6 @@
 /// Reselect the item with the given name in the current entries, if present.
+/// While a large directory is still streaming in, a name that has not
+/// arrived yet is selected when its page does.
 pub fn reselect_by_name(
     app: &mut crate::app::App,
     name: &str,
@@ -8,4 +10,12 @@ pub fn reselect_by_name(
     {
         app.list_state.select(Some(idx));
     }
+    else if app.listing_stream.is_some()
+    {
+        let fallback = app.selected_entry().map(|e| e.name.clone());
+        if let Some(stream) = app.listing_stream.as_mut()
+        {
+            stream.want(name, fallback);
+        }
+    }
 }
@@ -0,0 +1,86 @@
+//! Aggregate size of the marked entries for the header summary.
+//!
+//! File sizes are read once per selection and cached under a key derived
+//! from the selected paths, so redraws while scrolling do not repeat
+//! `metadata` calls. Directories contribute their background size from
+//! [`DirSizeCache`] once it is known (see `ui.dir_sizes`).
+
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    fs,
+    hash::{
+        DefaultHasher,
+        Hash,
+        Hasher,
+    },
+    path::PathBuf,
+    time::SystemTime,
+};
+
+use crate::core::dir_sizes::DirSizeCache;
+
+struct Summary
+{
+    key:        u64,
+    file_bytes: u64,
+    dirs:       Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+#[derive(Default)]
+pub struct SelectionSizeCache
+{
+    summary: RefCell<Option<Summary>>,
+}
+
+impl SelectionSizeCache
+{
+    /// Total size in bytes of `selected`: file sizes plus any directory
+    /// sizes already computed in `dir_sizes`.
+    pub fn total(
+        &self,
+        selected: &HashSet<PathBuf>,
+        dir_sizes: &DirSizeCache,
+    ) -> u64
+    {
+        let key = selection_key(selected);
+        let mut slot = self.summary.borrow_mut();
+        if slot.as_ref().is_none_or(|s| s.key != key)
+        {
+            let mut file_bytes = 0u64;
+            let mut dirs = Vec::new();
+            for path in selected
+            {
+                let Ok(meta) = fs::metadata(path)
+                else
+                {
+                    continue;
+                };
+                if meta.is_dir()
+                {
+                    dirs.push((path.clone(), meta.modified().ok()));
+                }
+                else
+                {
+                    file_bytes = file_bytes.saturating_add(meta.len());
+                }
+            }
+            *slot = Some(Summary { key, file_bytes, dirs });
+        }
+        let s = slot.as_ref().expect("summary computed above");
+        s.dirs
+            .iter()
+            .filter_map(|(p, mtime)| dir_sizes.get(p, *mtime))
+            .fold(s.file_bytes, u64::saturating_add)
+    }
+}
+
+/// Order-independent fingerprint of the selected paths.
+fn selection_key(selected: &HashSet<PathBuf>) -> u64
+{
+    selected.iter().fold(selected.len() as u64, |acc, p| {
+        let mut h = DefaultHasher::new();
+        p.hash(&mut h);
+        acc.wrapping_add(h.finish())
+    })
+}
@@ -0,0 +1,87 @@
+//! `tree`-style text rendering of a directory, bounded by depth and size.
+
+use std::path::Path;
+
+use crate::actions::internal::SortKey;
+
+/// Render `root` and its descendants up to `max_depth` levels as text using
+/// box-drawing connectors, directories first. At most `max_lines` entry lines
+/// are produced; symlinked directories are listed but not descended into.
+pub fn tree_text(
+    root: &Path,
+    max_depth: usize,
+    show_hidden: bool,
+    max_lines: usize,
+) -> String
+{
+    let name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.display().to_string());
+    let mut out = format!("{}/\n", name);
+    let mut lines = 0usize;
+    walk(root, "", 1, max_depth, show_hidden, max_lines, &mut lines, &mut out);
+    if lines >= max_lines
+    {
+        out.push_str("…\n");
+    }
+    out
+}
+
+#[allow(clippy::too_many_arguments)]
+fn walk(
+    dir: &Path,
+    prefix: &str,
+    depth: usize,
+    max_depth: usize,
+    show_hidden: bool,
+    max_lines: usize,
+    lines: &mut usize,
+    out: &mut String,
+)
+{
+    if depth > max_depth
+    {
+        return;
+    }
+    let Ok(entries) = crate::core::listing::read_dir_sorted(
+        dir,
+        show_hidden,
+        SortKey::Name,
+        false,
+        false,
+        usize::MAX,
+    )
+    else
+    {
+        return;
+    };
+    let count = entries.len();
+    for (i, e) in entries.into_iter().enumerate()
+    {
+        if *lines >= max_lines
+        {
+            return;
+        }
+        let last = i + 1 == count;
+        let connector = if last { "└── " } else { "├── " };
+        let marker = if e.is_dir { "/" } else { "" };
+        out.push_str(&format!("{}{}{}{}\n", prefix, connector, e.name, marker));
+        *lines += 1;
+        if e.is_dir && !e.is_symlink
+        {
+            let child_prefix =
+                format!("{}{}", prefix, if last { "    " } else { "│   " });
+            walk(
+                &e.path,
+                &child_prefix,
+                depth + 1,
+                max_depth,
+                show_hidden,
+                max_lines,
+                lines,
+                out,
+            );
+        }
+    }
+}
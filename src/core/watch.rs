@@ -0,0 +1,221 @@
+//! Optional filesystem watchers that keep the current listing and the theme
+//! file fresh.
+//!
+//! The watcher only flips a shared flag from notify's callback thread; the
+//! runtime loop polls it via [`poll`] and performs the actual refresh so all
+//! `App` mutation stays on the UI thread.
+
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::{
+        Arc,
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use notify::{
+    RecursiveMode,
+    Watcher,
+};
+
+/// Minimum interval between two refreshes triggered by filesystem events.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub struct DirWatcher
+{
+    _watcher:     notify::RecommendedWatcher,
+    dir:          PathBuf,
+    file:         Option<PathBuf>,
+    dirty:        Arc<AtomicBool>,
+    last_refresh: Option<Instant>,
+}
+
+impl DirWatcher
+{
+    /// Start watching `dir` (non-recursively).
+    pub fn new(dir: &Path) -> notify::Result<Self>
+    {
+        Self::watch(dir, None)
+    }
+
+    /// Watch a single file. Its parent directory is watched instead of the
+    /// file itself so editors that save by replacing the file are noticed.
+    pub fn for_file(file: &Path) -> notify::Result<Self>
+    {
+        let dir = file.parent().unwrap_or_else(|| Path::new("."));
+        Self::watch(dir, Some(file.to_path_buf()))
+    }
+
+    fn watch(
+        dir: &Path,
+        file: Option<PathBuf>,
+    ) -> notify::Result<Self>
+    {
+        let dirty = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&dirty);
+        let name =
+            file.as_ref().and_then(|f| f.file_name()).map(|n| n.to_owned());
+        let mut watcher = notify::recommended_watcher(
+            move |res: notify::Result<notify::Event>| {
+                if let Ok(ev) = res
+                    && !ev.kind.is_access()
+                    && name.as_ref().is_none_or(|n| {
+                        ev.paths.iter().any(|p| p.file_name() == Some(n))
+                    })
+                {
+                    flag.store(true, Ordering::Relaxed);
+                }
+            },
+        )?;
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        Ok(Self {
+            _watcher: watcher,
+            dir: dir.to_path_buf(),
+            file,
+            dirty,
+            last_refresh: None,
+        })
+    }
+
+    pub fn dir(&self) -> &Path
+    {
+        &self.dir
+    }
+
+    /// The watched file, for watchers created with [`DirWatcher::for_file`].
+    pub fn file(&self) -> Option<&Path>
+    {
+        self.file.as_deref()
+    }
+
+    /// Returns true when changes were observed and the debounce window since
+    /// the previous refresh has elapsed. Consumes the pending flag.
+    pub fn take_due(&mut self) -> bool
+    {
+        if !self.dirty.load(Ordering::Relaxed)
+        {
+            return false;
+        }
+        if let Some(at) = self.last_refresh
+            && at.elapsed() < DEBOUNCE
+        {
+            return false;
+        }
+        self.dirty.store(false, Ordering::Relaxed);
+        self.last_refresh = Some(Instant::now());
+        true
+    }
+}
+
+/// Keep the watcher in sync with `ui.auto_refresh` and the current directory,
+/// and refresh the listing when changes are pending. Returns true when the
+/// listing was refreshed.
+pub fn poll(app: &mut crate::app::App) -> bool
+{
+    if !app.config.ui.auto_refresh
+    {
+        app.watcher = None;
+        return false;
+    }
+    let stale =
+        app.watcher.as_ref().map(|w| w.dir() != app.cwd).unwrap_or(true);
+    if stale
+    {
+        // Retrying every loop iteration would spin on a directory that
+        // cannot be watched; wait for the cwd to change instead
+        if app.watch_failed.as_deref() == Some(app.cwd.as_path())
+        {
+            return false;
+        }
+        app.watcher = match DirWatcher::new(&app.cwd)
+        {
+            Ok(w) =>
+            {
+                app.watch_failed = None;
+                Some(w)
+            }
+            Err(e) =>
+            {
+                crate::trace::log(format!(
+                    "[watch] failed to watch {}: {}",
+                    app.cwd.display(),
+                    e
+                ));
+                app.watch_failed = Some(app.cwd.clone());
+                None
+            }
+        };
+        return false;
+    }
+    let due = app.watcher.as_mut().map(|w| w.take_due()).unwrap_or(false);
+    if !due
+    {
+        return false;
+    }
+    let current_name = app.selected_entry().map(|e| e.name.clone());
+    app.refresh_lists();
+    if let Some(name) = current_name
+    {
+        crate::core::selection::reselect_by_name(app, &name);
+    }
+    app.refresh_preview();
+    true
+}
+
+/// With `ui.auto_refresh` on, watch the current `ui.theme_path` and reload
+/// the theme when the file changes. Returns true when it was reloaded.
+pub fn poll_theme(app: &mut crate::app::App) -> bool
+{
+    let path = match app.config.ui.theme_path.clone()
+    {
+        Some(p) if app.config.ui.auto_refresh => p,
+        _ =>
+        {
+            app.theme_watcher = None;
+            return false;
+        }
+    };
+    let stale = app
+        .theme_watcher
+        .as_ref()
+        .map(|w| w.file() != Some(path.as_path()))
+        .unwrap_or(true);
+    if stale
+    {
+        if app.theme_watch_failed.as_deref() == Some(path.as_path())
+        {
+            return false;
+        }
+        app.theme_watcher = match DirWatcher::for_file(&path)
+        {
+            Ok(w) =>
+            {
+                app.theme_watch_failed = None;
+                Some(w)
+            }
+            Err(e) =>
+            {
+                crate::trace::log(format!(
+                    "[watch] failed to watch theme {}: {}",
+                    path.display(),
+                    e
+                ));
+                app.theme_watch_failed = Some(path);
+                None
+            }
+        };
+        return false;
+    }
+    let due = app.theme_watcher.as_mut().map(|w| w.take_due()).unwrap_or(false);
+    due && app.reload_theme()
+}
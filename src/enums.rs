@@ -9,6 +9,7 @@ pub(crate) fn sort_key_to_str(k: crate::actions::SortKey) -> &'static str
         crate::actions::SortKey::Size => "size",
         crate::actions::SortKey::MTime => "mtime",
         crate::actions::SortKey::CTime => "created",
+        crate::actions::SortKey::ATime => "atime",
     }
 }
 
@@ -27,6 +28,7 @@ pub(crate) fn sort_key_from_str(s: &str) -> Option<crate::actions::SortKey>
         {
             Some(crate::actions::SortKey::CTime)
         }
+        "atime" | "accessed" | "a" => Some(crate::actions::SortKey::ATime),
         _ => None,
     }
 }
@@ -41,6 +43,7 @@ pub(crate) fn info_mode_to_str(m: crate::app::InfoMode)
         crate::app::InfoMode::Size => Some("size"),
         crate::app::InfoMode::Created => Some("created"),
         crate::app::InfoMode::Modified => Some("modified"),
+        crate::app::InfoMode::Entries => Some("entries"),
     }
 }
 
@@ -53,10 +56,37 @@ pub(crate) fn info_mode_from_str(s: &str) -> Option<crate::app::InfoMode>
         "size" | "bytes" => Some(crate::app::InfoMode::Size),
         "created" | "ctime" | "birth" => Some(crate::app::InfoMode::Created),
         "modified" | "mtime" => Some(crate::app::InfoMode::Modified),
+        "entries" | "count" | "children" => Some(crate::app::InfoMode::Entries),
         _ => None,
     }
 }
 
+#[inline]
+pub(crate) fn case_mode_from_str(s: &str) -> Option<crate::app::CaseMode>
+{
+    match s.trim().to_ascii_lowercase().as_str()
+    {
+        "smart" | "smart-case" => Some(crate::app::CaseMode::Smart),
+        "sensitive" | "case-sensitive" => Some(crate::app::CaseMode::Sensitive),
+        "insensitive" | "case-insensitive" =>
+        {
+            Some(crate::app::CaseMode::Insensitive)
+        }
+        _ => None,
+    }
+}
+
+#[inline]
+pub(crate) fn case_mode_to_str(m: crate::app::CaseMode) -> &'static str
+{
+    match m
+    {
+        crate::app::CaseMode::Smart => "smart-case",
+        crate::app::CaseMode::Sensitive => "case-sensitive",
+        crate::app::CaseMode::Insensitive => "case-insensitive",
+    }
+}
+
 #[inline]
 pub(crate) fn display_mode_to_str(d: crate::app::DisplayMode) -> &'static str
 {
@@ -0,0 +1,65 @@
+//! Process exit codes for scripting.
+//!
+//! `main` maps any error that aborts lsv to one of these codes via
+//! [`code_for_error`] so wrapper scripts can tell failure kinds apart. A
+//! session that ends normally exits via [`code_for_exit`]: 0, or [`CONFIG`]
+//! when it ran on defaults because the configuration failed to load.
+
+use std::{
+    error::Error,
+    io,
+};
+
+/// Unclassified failure.
+pub const FAILURE: i32 = 1;
+/// Invalid command-line usage.
+pub const USAGE: i32 = 2;
+/// Configuration could not be loaded or executed.
+pub const CONFIG: i32 = 3;
+/// Filesystem or terminal I/O failure.
+pub const IO: i32 = 4;
+/// A user-requested action (e.g. a Lua action) failed.
+pub const ACTION: i32 = 5;
+
+/// Exit code for a session that ended normally: [`CONFIG`] while the last
+/// configuration load failed (lsv starts on defaults and reports the error),
+/// else 0.
+pub fn code_for_exit(app: &crate::app::App) -> i32
+{
+    if app.read_config_error().is_some() { CONFIG } else { 0 }
+}
+
+/// Classify an error into one of the exit codes above.
+///
+/// Configuration and action failures travel as [`io::Error`]s wrapping
+/// [`ConfigError`](crate::config::ConfigError) or
+/// [`ActionError`](crate::actions::ActionError); any other `io::Error` maps to
+/// [`IO`].
+pub fn code_for_error(err: &(dyn Error + 'static)) -> i32
+{
+    let inner: &(dyn Error + 'static) = match err.downcast_ref::<io::Error>()
+    {
+        Some(io_err) => match io_err.get_ref()
+        {
+            Some(inner) => inner,
+            None => return IO,
+        },
+        None => err,
+    };
+    if inner.is::<crate::config::ConfigError>()
+    {
+        CONFIG
+    }
+    else if inner.is::<crate::actions::ActionError>()
+    {
+        ACTION
+    }
+    else if err.is::<io::Error>()
+    {
+        IO
+    }
+    else
+    {
+        FAILURE
+    }
+}
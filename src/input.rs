@@ -62,6 +62,41 @@ pub fn handle_key(
         return Ok(false);
     }
 
+    if matches!(app.overlay, crate::app::Overlay::TemplatePicker(_))
+    {
+        match key.code
+        {
+            KeyCode::Esc =>
+            {
+                app.overlay = crate::app::Overlay::None;
+                app.force_full_redraw = true;
+            }
+            KeyCode::Enter =>
+            {
+                app.confirm_template_picker();
+            }
+            KeyCode::Up | KeyCode::Char('k') =>
+            {
+                app.template_picker_move(-1);
+            }
+            KeyCode::Down | KeyCode::Char('j') =>
+            {
+                app.template_picker_move(1);
+            }
+            KeyCode::PageUp =>
+            {
+                app.template_picker_move(-5);
+            }
+            KeyCode::PageDown =>
+            {
+                app.template_picker_move(5);
+            }
+            _ =>
+            {}
+        }
+        return Ok(false);
+    }
+
     // Prompt overlay input handling
     if let crate::app::Overlay::Prompt(ref mut st_box) = app.overlay
     {
@@ -81,6 +116,8 @@ pub fn handle_key(
             }
             KeyCode::Enter =>
             {
+                // Per-item results of a bulk rename for `ui.bulk_report`
+                let mut report: Option<Vec<String>> = None;
                 // Submit
                 match st.kind
                 {
@@ -104,6 +141,50 @@ pub fn handle_key(
                             app.refresh_lists();
                         }
                     }
+                    crate::app::PromptKind::GotoPath =>
+                    {
+                        let input = st.input.clone();
+                        app.goto_path(&input);
+                    }
+                    crate::app::PromptKind::GotoLine =>
+                    {
+                        let input = st.input.clone();
+                        app.goto_line(&input);
+                    }
+                    crate::app::PromptKind::Touch =>
+                    {
+                        let name = st.input.trim().to_string();
+                        if !name.is_empty()
+                        {
+                            app.touch_named(&name);
+                        }
+                    }
+                    crate::app::PromptKind::NewFromTemplate(ref template) =>
+                    {
+                        let name = st.input.trim().to_string();
+                        let template = template.clone();
+                        if !name.is_empty()
+                        {
+                            app.create_from_template(&template, &name);
+                        }
+                    }
+                    crate::app::PromptKind::TransferTo(op) =>
+                    {
+                        let input = st.input.clone();
+                        app.transfer_selected_to(&input, op);
+                    }
+                    crate::app::PromptKind::Filter(kind) =>
+                    {
+                        let pattern = st.input.trim().to_string();
+                        if pattern.is_empty()
+                        {
+                            app.clear_list_filter();
+                        }
+                        else
+                        {
+                            app.set_list_filter(kind, &pattern);
+                        }
+                    }
                     crate::app::PromptKind::MarkAdd =>
                     {
                         let name = st.input.trim();
@@ -146,30 +227,66 @@ pub fn handle_key(
                                 tpl[..pos].to_string(),
                                 tpl[pos + 2..].to_string(),
                             );
+                            let mut lines = Vec::new();
+                            let (mut ok, mut errs) = (0usize, 0usize);
                             for p in items.iter()
                             {
-                                if let Some(name_os) = p.file_name()
-                                    && let Some(name) = name_os.to_str()
+                                let Some(name) =
+                                    p.file_name().and_then(|n| n.to_str())
+                                else
+                                {
+                                    lines.push(format!(
+                                        "Skip (not UTF-8): {}",
+                                        p.display()
+                                    ));
+                                    continue;
+                                };
+                                // Extract variable segment using original
+                                // pre/suf
+                                let var = name
+                                    .strip_prefix(pre.as_str())
+                                    .unwrap_or(name)
+                                    .strip_suffix(suf.as_str())
+                                    .unwrap_or(name);
+                                let new_name =
+                                    format!("{}{}{}", new_pre, var, new_suf);
+                                let dst = app.cwd.join(new_name);
+                                match std::fs::rename(p, &dst)
                                 {
-                                    // Extract variable segment using original
-                                    // pre/suf
-                                    let var = name
-                                        .strip_prefix(pre.as_str())
-                                        .unwrap_or(name)
-                                        .strip_suffix(suf.as_str())
-                                        .unwrap_or(name);
-                                    let new_name = format!(
-                                        "{}{}{}",
-                                        new_pre, var, new_suf
-                                    );
-                                    let dst = app.cwd.join(new_name);
-                                    if std::fs::rename(p, &dst).is_ok()
-                                        && app.selected.remove(p)
+                                    Ok(()) =>
                                     {
-                                        app.selected.insert(dst.clone());
+                                        ok += 1;
+                                        lines.push(format!(
+                                            "Renamed: {} -> {}",
+                                            p.display(),
+                                            dst.display()
+                                        ));
+                                        if app.selected.remove(p)
+                                        {
+                                            app.selected.insert(dst);
+                                        }
+                                    }
+                                    Err(e) =>
+                                    {
+                                        errs += 1;
+                                        lines.push(format!(
+                                            "Error: {} -> {}: {}",
+                                            p.display(),
+                                            dst.display(),
+                                            e
+                                        ));
                                     }
                                 }
                             }
+                            let summary = format!(
+                                "Rename: ok={} skipped={} errors={}",
+                                ok,
+                                lines.len() - ok - errs,
+                                errs
+                            );
+                            app.add_message(&summary);
+                            lines.push(summary);
+                            report = Some(lines);
                             app.refresh_lists();
                         }
                         else
@@ -183,6 +300,22 @@ pub fn handle_key(
                 }
                 app.overlay = crate::app::Overlay::None;
                 app.force_full_redraw = true;
+                if let Some(lines) = report
+                    && app.config.ui.bulk_report
+                {
+                    app.display_output("Rename results", &lines.join("\n"));
+                }
+            }
+            KeyCode::Tab
+                if matches!(
+                    st.kind,
+                    crate::app::PromptKind::GotoPath
+                        | crate::app::PromptKind::TransferTo(_)
+                ) =>
+            {
+                st.input = crate::util::complete_dir_path(&st.input, &app.cwd);
+                st.cursor = st.input.len();
+                app.force_full_redraw = true;
             }
             KeyCode::Backspace
                 if st.cursor > 0 && st.cursor <= st.input.len() =>
@@ -427,7 +560,7 @@ pub fn handle_key(
         enum Act
         {
             None,
-            DeleteAll,
+            Yes,
         }
         let mut act = Act::None;
         match key.code
@@ -439,13 +572,13 @@ pub fn handle_key(
             }
             KeyCode::Enter if st.default_yes =>
             {
-                act = Act::DeleteAll;
+                act = Act::Yes;
             }
             KeyCode::Enter =>
             {}
             KeyCode::Char('y') | KeyCode::Char('Y') =>
             {
-                act = Act::DeleteAll;
+                act = Act::Yes;
             }
             KeyCode::Char('n') | KeyCode::Char('N') =>
             {
@@ -455,24 +588,78 @@ pub fn handle_key(
             _ =>
             {}
         }
-        let clear_sel = matches!(key.code, KeyCode::Esc);
         // Drop borrow before mutating app
         let kind = st.kind.clone();
+        let clear_sel = matches!(key.code, KeyCode::Esc)
+            && matches!(kind, crate::app::ConfirmKind::DeleteSelected(_));
         app.overlay = crate::app::Overlay::None;
         app.force_full_redraw = true;
         if clear_sel
         {
             app.clear_all_selected();
         }
-        if let (Act::DeleteAll, crate::app::ConfirmKind::DeleteSelected(list)) =
-            (act, &kind)
+        if let Act::Yes = act
         {
-            for p in list.iter()
+            match kind
             {
-                app.perform_delete_path(p);
+                crate::app::ConfirmKind::DeleteSelected(list) =>
+                {
+                    app.perform_delete_paths(&list);
+                }
+                crate::app::ConfirmKind::RunAction(action) =>
+                {
+                    let action =
+                        app.get_keymap_action(&action).unwrap_or(action);
+                    crate::trace::log(format!("[confirm] yes -> '{}'", action));
+                    // A failing action is reported, not fatal (as for keys)
+                    if let Err(e) =
+                        crate::actions::dispatch_action(app, &action)
+                    {
+                        app.add_message(&format!("Error: {}", e));
+                    }
+                }
             }
         }
-        return Ok(false);
+        return Ok(app.should_quit);
+    }
+
+    // Visual mode: Enter marks the range, Esc leaves without marking
+    if app.visual_anchor.is_some()
+    {
+        match key.code
+        {
+            KeyCode::Enter =>
+            {
+                app.commit_visual_select();
+                return Ok(false);
+            }
+            KeyCode::Esc =>
+            {
+                app.keys.pending.clear();
+                app.cancel_visual_select();
+                return Ok(false);
+            }
+            _ =>
+            {}
+        }
+    }
+
+    // Arrow and page keys scroll the messages overlay while it is open
+    if matches!(app.overlay, crate::app::Overlay::Messages)
+    {
+        let delta = match key.code
+        {
+            KeyCode::Up => Some(1),
+            KeyCode::Down => Some(-1),
+            KeyCode::PageUp => Some(10),
+            KeyCode::PageDown => Some(-10),
+            _ => None,
+        };
+        if let Some(d) = delta
+        {
+            app.scroll_messages(d);
+            return Ok(false);
+        }
     }
 
     // First, try dynamic key mappings with simple sequence support
@@ -489,27 +676,25 @@ pub fn handle_key(
         return Ok(false);
     }
 
-    if let KeyCode::Char(ch) = key.code
+    // <Esc> keeps its own handling below so it always cancels sequences
+    if key.code != KeyCode::Esc
+        && let Some(tok) =
+            crate::keymap::build_key_token(key.code, key.modifiers)
     {
-        // Allow modifier combinations; build token string for sequence matching
+        // Allow modifier combinations and named keys (<Up>, <F5>, ...);
+        // unmapped special keys fall through to the built-in handling below
         {
             let now = std::time::Instant::now();
-            // reset pending_seq on timeout
-            if app.config.keys.sequence_timeout_ms > 0
+            // reset pending_seq on timeout (per-mapping overrides first)
+            if !app.keys.pending.is_empty()
                 && let Some(last) = app.keys.last_at
+                && let Some(timeout) = app.sequence_timeout(&app.keys.pending)
+                && now.duration_since(last) > timeout
             {
-                let timeout = std::time::Duration::from_millis(
-                    app.config.keys.sequence_timeout_ms,
-                );
-                if now.duration_since(last) > timeout
-                {
-                    app.keys.pending.clear();
-                }
+                app.keys.pending.clear();
             }
             app.keys.last_at = Some(now);
 
-            // Build token
-            let tok = crate::keymap::build_token(ch, key.modifiers);
             app.keys.pending.push_str(&tok);
             let seq = app.keys.pending.clone();
 
@@ -565,6 +750,7 @@ pub fn handle_key(
             }
             // cancel pending sequences and which-key
             app.keys.pending.clear();
+            app.clear_list_filter();
             app.overlay = crate::app::Overlay::None;
             app.clear_all_selected();
             return Ok(false);
@@ -594,44 +780,18 @@ pub fn handle_key(
                 app.refresh_preview();
             }
         }
-        (KeyCode::Enter, _) | (KeyCode::Right, _) =>
+        (KeyCode::Enter, _) =>
         {
-            if let Some(entry) = app.selected_entry()
-                && entry.is_dir
-            {
-                app.cwd = entry.path.clone();
-                app.refresh_lists();
-                if app.current_entries.is_empty()
-                {
-                    app.list_state.select(None);
-                }
-                else
-                {
-                    app.list_state.select(Some(0));
-                }
-                app.refresh_preview();
-            }
+            app.open_selected();
+        }
+        // Unmapped arrows fall back to the navigation actions
+        (KeyCode::Right, _) =>
+        {
+            crate::actions::dispatch_action(app, "nav:enter")?;
         }
         (KeyCode::Backspace, _) | (KeyCode::Left, _) =>
         {
-            if let Some(parent) = app.cwd.parent()
-            {
-                // Remember the directory name we are leaving so we can reselect
-                // it
-                let just_left = app
-                    .cwd
-                    .file_name()
-                    .map(|s| s.to_string_lossy().to_string());
-                app.cwd = parent.to_path_buf();
-                app.refresh_lists();
-                if let Some(name) = just_left
-                    && let Some(idx) =
-                        app.current_entries.iter().position(|e| e.name == name)
-                {
-                    app.list_state.select(Some(idx));
-                }
-                app.refresh_preview();
-            }
+            crate::actions::dispatch_action(app, "nav:leave")?;
         }
         _ =>
         {}
@@ -1,7 +1,11 @@
-use crossterm::event::KeyModifiers;
+use crossterm::event::{
+    KeyCode,
+    KeyModifiers,
+};
 
 /// Split a key sequence string into tokens, preserving modifier tokens like
-/// "<C-x>" as single units.
+/// "<C-x>" and named keys like "<F5>" as single units. Named keys are
+/// canonicalised, so "<pgup>" and "<PageUp>" produce the same token.
 pub fn tokenize_sequence(seq: &str) -> Vec<String>
 {
     let mut toks = Vec::new();
@@ -13,7 +17,7 @@ pub fn tokenize_sequence(seq: &str) -> Vec<String>
             && let Some(j) = seq[i + 1..].find('>')
         {
             let end = i + 1 + j + 1;
-            toks.push(seq[i..end].to_string());
+            toks.push(canonical_token(&seq[i..end]));
             i = end;
             continue;
         }
@@ -63,3 +67,147 @@ pub fn build_token(
         ch.to_string()
     }
 }
+
+/// Build a key token for any key event. Characters go through
+/// [`build_token`]; special keys become named tokens such as "<Up>",
+/// "<F5>" or "<C-Home>". Returns `None` for keys that cannot be mapped.
+pub fn build_key_token(
+    code: KeyCode,
+    mods: KeyModifiers,
+) -> Option<String>
+{
+    let (name, mods) = match code
+    {
+        KeyCode::Char(ch) => return Some(build_token(ch, mods)),
+        KeyCode::F(n) => (format!("F{}", n), mods),
+        KeyCode::BackTab => ("BackTab".to_string(), mods - KeyModifiers::SHIFT),
+        other => (key_name(other)?.to_string(), mods),
+    };
+    Some(named_token(&name, mods))
+}
+
+fn key_name(code: KeyCode) -> Option<&'static str>
+{
+    Some(match code
+    {
+        KeyCode::Up => "Up",
+        KeyCode::Down => "Down",
+        KeyCode::Left => "Left",
+        KeyCode::Right => "Right",
+        KeyCode::Home => "Home",
+        KeyCode::End => "End",
+        KeyCode::PageUp => "PageUp",
+        KeyCode::PageDown => "PageDown",
+        KeyCode::Tab => "Tab",
+        KeyCode::Enter => "Enter",
+        KeyCode::Backspace => "Backspace",
+        KeyCode::Delete => "Delete",
+        KeyCode::Insert => "Insert",
+        KeyCode::Esc => "Esc",
+        _ => return None,
+    })
+}
+
+/// Canonical spelling of a named key, accepting common aliases in any case.
+fn canonical_key_name(name: &str) -> Option<String>
+{
+    let low = name.to_ascii_lowercase();
+    let canon = match low.as_str()
+    {
+        "up" => "Up",
+        "down" => "Down",
+        "left" => "Left",
+        "right" => "Right",
+        "home" => "Home",
+        "end" => "End",
+        "pageup" | "pgup" => "PageUp",
+        "pagedown" | "pgdn" | "pgdown" => "PageDown",
+        "tab" => "Tab",
+        "backtab" => "BackTab",
+        "enter" | "cr" | "return" => "Enter",
+        "backspace" | "bs" => "Backspace",
+        "delete" | "del" => "Delete",
+        "insert" | "ins" => "Insert",
+        "esc" | "escape" => "Esc",
+        _ =>
+        {
+            let n: u8 = low.strip_prefix('f')?.parse().ok()?;
+            return (1..=24).contains(&n).then(|| format!("F{}", n));
+        }
+    };
+    Some(canon.to_string())
+}
+
+fn named_token(
+    name: &str,
+    mods: KeyModifiers,
+) -> String
+{
+    let mut tok = String::from("<");
+    if mods.contains(KeyModifiers::CONTROL)
+    {
+        tok.push_str("C-");
+    }
+    if mods.contains(KeyModifiers::ALT)
+    {
+        tok.push_str("M-");
+    }
+    if mods.contains(KeyModifiers::SUPER)
+    {
+        tok.push_str("S-");
+    }
+    if mods.contains(KeyModifiers::SHIFT)
+    {
+        tok.push_str("Sh-");
+    }
+    tok.push_str(name);
+    tok.push('>');
+    tok
+}
+
+/// Rewrite a bracketed token naming a special key into its canonical form
+/// (modifiers in C-, M-, S-, Sh- order). Other tokens are returned as-is.
+fn canonical_token(tok: &str) -> String
+{
+    let inner = &tok[1..tok.len() - 1];
+    let mut mods = KeyModifiers::NONE;
+    let mut rest = inner;
+    loop
+    {
+        let (flag, len) = if rest.starts_with("Sh-")
+        {
+            (KeyModifiers::SHIFT, 3)
+        }
+        else if rest.starts_with("C-")
+        {
+            (KeyModifiers::CONTROL, 2)
+        }
+        else if rest.starts_with("M-")
+        {
+            (KeyModifiers::ALT, 2)
+        }
+        else if rest.starts_with("S-")
+        {
+            (KeyModifiers::SUPER, 2)
+        }
+        else
+        {
+            break;
+        };
+        if rest.len() <= len
+        {
+            break;
+        }
+        mods |= flag;
+        rest = &rest[len..];
+    }
+    if rest.chars().count() < 2
+    {
+        return tok.to_string();
+    }
+    match canonical_key_name(rest)
+    {
+        Some(name) => named_token(&name, mods),
+        None => tok.to_string(),
+    }
+}
@@ -15,6 +15,7 @@ pub use crate::config::load_config_from_code;
 pub use crate::config::runtime::data as config_data;
 pub mod core;
 pub mod enums;
+pub mod exit;
 pub mod input;
 pub mod keymap;
 pub mod runtime;
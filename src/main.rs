@@ -5,6 +5,7 @@ mod config;
 mod core;
 mod embed_examples;
 mod enums;
+mod exit;
 mod input;
 mod keymap;
 mod runtime;
@@ -28,8 +29,9 @@ fn print_help()
          exit\n--config-dir DIR  Use DIR as the config root (sets \
          LSV_CONFIG_DIR)\n--init-config     Prompt to create user config from \
          examples\n--trace[=FILE]    Enable tracing to FILE (default \
-         /tmp/lsv-trace.log)\nArguments:\nDIR                   Start in \
-         directory DIR (default: current dir)\n"
+         /tmp/lsv-trace.log)\n--no-altscreen    Draw in the main terminal \
+         buffer (keeps output visible on exit)\nArguments:\nDIR                   \
+         Start in directory DIR (default: current dir)\n"
     );
 }
 
@@ -129,6 +131,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>>
     let mut args = env::args().skip(1);
     let mut dir_arg: Option<String> = None;
     let mut init_config: bool = false;
+    let mut no_altscreen: bool = false;
     while let Some(a) = args.next()
     {
         match a.as_str()
@@ -164,6 +167,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>>
             {
                 init_config = true;
             }
+            "--no-altscreen" =>
+            {
+                no_altscreen = true;
+            }
             "--config-dir" =>
             {
                 if let Some(dir) = args.next()
@@ -174,7 +181,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>>
                 {
                     eprintln!("lsv: --config-dir requires a DIR argument");
                     print_help();
-                    std::process::exit(2);
+                    std::process::exit(exit::USAGE);
                 }
             }
             s if s.starts_with("--config-dir=") =>
@@ -194,7 +201,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>>
             {
                 eprintln!("lsv: unknown option: {}", s);
                 print_help();
-                std::process::exit(2);
+                std::process::exit(exit::USAGE);
             }
             // Positional directory
             other =>
@@ -250,19 +257,37 @@ fn main() -> Result<(), Box<dyn std::error::Error>>
         return Ok(());
     }
 
-    if let Some(dir) = dir_arg
-        && let Err(e) = std::env::set_current_dir(&dir)
+    if let Some(dir) = dir_arg.as_deref()
+        && let Err(e) = std::env::set_current_dir(dir)
     {
         eprintln!("lsv: failed to change directory to '{}': {}", dir, e);
-        std::process::exit(1);
+        std::process::exit(exit::IO);
     }
 
     trace::log("[main] starting lsv");
-    let mut app = App::new()?;
+    let started =
+        if dir_arg.is_some() { App::new() } else { App::new_at_start_dir() };
+    let mut app = match started
+    {
+        Ok(app) => app,
+        Err(e) =>
+        {
+            trace::log(format!("[error] App::new: {e}"));
+            eprintln!("lsv: {}", e);
+            std::process::exit(exit::code_for_error(&e));
+        }
+    };
+    app.set_screen_mode(runtime::ScreenMode::from_no_altscreen(no_altscreen));
     if let Err(e) = runtime::run_app(&mut app)
     {
         trace::log(format!("[error] runtime::run_app: {e}"));
-        return Err(e);
+        eprintln!("lsv: {}", e);
+        std::process::exit(exit::code_for_error(e.as_ref()));
+    }
+    let code = exit::code_for_exit(&app);
+    if code != 0
+    {
+        std::process::exit(code);
     }
     Ok(())
 }
@@ -21,6 +21,31 @@ use ratatui::{
 
 use crate::app::App;
 
+/// Terminal buffer used while the TUI is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenMode
+{
+    /// Draw in the alternate screen (default); output is cleared on exit.
+    Alternate,
+    /// Draw in the main buffer so trailing output stays visible
+    /// (`--no-altscreen`).
+    Main,
+}
+
+impl ScreenMode
+{
+    /// Select the screen mode from the `--no-altscreen` flag.
+    pub fn from_no_altscreen(no_altscreen: bool) -> Self
+    {
+        if no_altscreen { ScreenMode::Main } else { ScreenMode::Alternate }
+    }
+
+    pub fn uses_alternate(self) -> bool
+    {
+        matches!(self, ScreenMode::Alternate)
+    }
+}
+
 /// Process a single crossterm event and return `Ok(true)` if the app should
 /// exit.
 #[allow(dead_code)]
@@ -39,9 +64,13 @@ pub fn process_event(
 
 pub fn run_app(app: &mut App) -> Result<(), Box<dyn std::error::Error>>
 {
+    let alt_screen = app.screen_mode.uses_alternate();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    if alt_screen
+    {
+        execute!(stdout, EnterAlternateScreen)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
@@ -83,6 +112,17 @@ pub fn run_app(app: &mut App) -> Result<(), Box<dyn std::error::Error>>
                     }
                 }
             }
+            // Pick up filesystem changes when ui.auto_refresh is enabled
+            crate::core::watch::poll(app);
+            crate::core::watch::poll_theme(app);
+            // Merge pages of a large directory still being read
+            crate::core::listing::poll(app);
+            // Collect/queue background directory sizes (ui.dir_sizes)
+            crate::core::dir_sizes::poll(app);
+            // Report commands started with lsv.os_run_async that finished
+            crate::core::jobs::poll(app);
+            // Run a deferred preview once the selection has settled
+            app.flush_pending_preview();
             if app.force_full_redraw
             {
                 let _ = terminal.clear();
@@ -97,7 +137,21 @@ pub fn run_app(app: &mut App) -> Result<(), Box<dyn std::error::Error>>
                 result = Err(e.into());
                 break;
             }
-            match crossterm::event::poll(Duration::from_millis(200))
+            // Wake up in time for a pending debounced preview
+            let timeout = app
+                .preview_debounce_remaining()
+                .map(|d| d.min(Duration::from_millis(200)))
+                .unwrap_or(Duration::from_millis(200));
+            // Animate the header spinner while work is in progress
+            let timeout = if app.is_busy()
+            {
+                timeout.min(Duration::from_millis(100))
+            }
+            else
+            {
+                timeout
+            };
+            match crossterm::event::poll(timeout)
             {
                 Ok(true) => match event::read()
                 {
@@ -159,8 +213,14 @@ pub fn run_app(app: &mut App) -> Result<(), Box<dyn std::error::Error>>
         result
     };
 
+    // Write session state first, on error exits too, so a failing terminal
+    // restore below cannot lose it
+    app.flush_state();
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    if alt_screen
+    {
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    }
     terminal.show_cursor()?;
     // Clear caches tied to this session
     crate::ui::clear_owner_cache();
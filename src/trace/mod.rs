@@ -5,14 +5,55 @@ use std::{
     path::PathBuf,
 };
 
-fn enabled() -> bool
+/// Whether tracing is enabled (`LSV_TRACE` set to a non-empty, non-zero
+/// value).
+pub fn enabled() -> bool
 {
     std::env::var("LSV_TRACE")
         .map(|v| !v.is_empty() && v != "0")
         .unwrap_or(false)
 }
 
+/// Default size cap for the trace log before it is rotated.
+const DEFAULT_MAX_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Size cap from `LSV_TRACE_MAX_BYTES` (bytes; `0` disables rotation).
+fn max_bytes() -> u64
+{
+    std::env::var("LSV_TRACE_MAX_BYTES")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_BYTES)
+}
+
+/// Rename the log to `<name>.1` (replacing an older one) when appending
+/// `incoming` bytes would exceed the cap, so the next write starts afresh.
+fn rotate_if_needed(
+    path: &std::path::Path,
+    incoming: usize,
+)
+{
+    let cap = max_bytes();
+    if cap == 0
+    {
+        return;
+    }
+    let Ok(meta) = std::fs::metadata(path)
+    else
+    {
+        return;
+    };
+    if meta.len() + incoming as u64 > cap
+    {
+        let mut old = path.as_os_str().to_owned();
+        old.push(".1");
+        let _ = std::fs::rename(path, PathBuf::from(old));
+    }
+}
+
 /// Append a line to the trace log when tracing is enabled (`LSV_TRACE=1`).
+/// The log is rotated to `<name>.1` once it would grow past
+/// `LSV_TRACE_MAX_BYTES` (default 4 MiB).
 pub fn log<S: AsRef<str>>(s: S)
 {
     if !enabled()
@@ -22,6 +63,7 @@ pub fn log<S: AsRef<str>>(s: S)
     let line = format!("{} {}\n", now_millis(), s.as_ref());
     if let Some(path) = file_path()
     {
+        rotate_if_needed(&path, line.len());
         let _ =
             OpenOptions::new().create(true).append(true).open(path).and_then(
                 |mut f| {
@@ -67,7 +109,9 @@ pub fn install_panic_hook()
     }));
 }
 
-fn file_path() -> Option<PathBuf>
+/// Resolve the trace log path: `LSV_TRACE_FILE`, then `$TMPDIR/lsv-trace.log`,
+/// then the system temp directory.
+pub fn file_path() -> Option<PathBuf>
 {
     if let Ok(fp) = std::env::var("LSV_TRACE_FILE")
     {
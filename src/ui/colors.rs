@@ -1,4 +1,7 @@
-use ratatui::style::Color;
+use ratatui::style::{
+    Color,
+    Modifier,
+};
 
 pub fn parse_color(s: &str) -> Option<Color>
 {
@@ -17,25 +20,74 @@ pub fn parse_color(s: &str) -> Option<Color>
         "white" => Some(Color::White),
         _ =>
         {
-            // Try #RRGGBB
-            if let Some(rgb) = parse_hex_rgb(&low)
-            {
-                return Some(rgb);
-            }
-            None
+            // Try #RRGGBB / #RGB, then rgb(r, g, b)
+            parse_hex_rgb(&low).or_else(|| parse_rgb_fn(&low))
         }
     }
 }
 
+/// Combine theme modifier names (`bold`, `dim`, `italic`, `underlined`,
+/// `slow_blink`, `rapid_blink`, `reversed`, `hidden`, `crossed_out`, plus the
+/// aliases `underline`, `blink`, `reverse` and `strikethrough`). Unknown
+/// names are ignored.
+pub fn parse_modifiers(names: &[String]) -> Modifier
+{
+    names.iter().fold(Modifier::empty(), |acc, n| {
+        let m = match n.trim().to_ascii_lowercase().as_str()
+        {
+            "bold" => Modifier::BOLD,
+            "dim" => Modifier::DIM,
+            "italic" => Modifier::ITALIC,
+            "underlined" | "underline" => Modifier::UNDERLINED,
+            "slow_blink" | "blink" => Modifier::SLOW_BLINK,
+            "rapid_blink" => Modifier::RAPID_BLINK,
+            "reversed" | "reverse" => Modifier::REVERSED,
+            "hidden" => Modifier::HIDDEN,
+            "crossed_out" | "strikethrough" => Modifier::CROSSED_OUT,
+            _ => Modifier::empty(),
+        };
+        acc | m
+    })
+}
+
 fn parse_hex_rgb(s: &str) -> Option<Color>
 {
     let t = s.strip_prefix('#')?;
-    if t.len() != 6
+    if !t.is_ascii()
+    {
+        return None;
+    }
+    match t.len()
+    {
+        6 =>
+        {
+            let r = u8::from_str_radix(&t[0..2], 16).ok()?;
+            let g = u8::from_str_radix(&t[2..4], 16).ok()?;
+            let b = u8::from_str_radix(&t[4..6], 16).ok()?;
+            Some(Color::Rgb(r, g, b))
+        }
+        3 =>
+        {
+            // #RGB doubles each digit: #f80 == #ff8800
+            let r = u8::from_str_radix(&t[0..1], 16).ok()? * 0x11;
+            let g = u8::from_str_radix(&t[1..2], 16).ok()? * 0x11;
+            let b = u8::from_str_radix(&t[2..3], 16).ok()? * 0x11;
+            Some(Color::Rgb(r, g, b))
+        }
+        _ => None,
+    }
+}
+
+fn parse_rgb_fn(s: &str) -> Option<Color>
+{
+    let inner = s.strip_prefix("rgb(")?.strip_suffix(')')?;
+    let mut parts = inner.split(',').map(|p| p.trim().parse::<u8>());
+    let r = parts.next()?.ok()?;
+    let g = parts.next()?.ok()?;
+    let b = parts.next()?.ok()?;
+    if parts.next().is_some()
     {
         return None;
     }
-    let r = u8::from_str_radix(&t[0..2], 16).ok()?;
-    let g = u8::from_str_radix(&t[2..4], 16).ok()?;
-    let b = u8::from_str_radix(&t[4..6], 16).ok()?;
     Some(Color::Rgb(r, g, b))
 }
@@ -1,5 +1,14 @@
 use std::time::SystemTime;
 
+const SPINNER_FRAMES: [&str; 10] =
+    ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Spinner glyph for the given frame tick; cycles through the frames.
+pub fn spinner_frame(tick: usize) -> &'static str
+{
+    SPINNER_FRAMES[tick % SPINNER_FRAMES.len()]
+}
+
 pub fn human_size(bytes: u64) -> String
 {
     const UNITS: [&str; 7] = ["B", "KB", "MB", "GB", "TB", "PB", "EB"];
@@ -38,6 +38,10 @@ pub fn draw(
         .constraints([Constraint::Length(1), Constraint::Min(1)])
         .split(full);
 
+    if app.is_busy()
+    {
+        app.spinner_tick = app.spinner_tick.wrapping_add(1);
+    }
     draw_header(f, vchunks[0], app);
 
     let constraints = panes::pane_constraints(app);
@@ -48,7 +52,10 @@ pub fn draw(
 
     panes::draw_parent_panel(f, chunks[0], app);
     panes::draw_current_panel(f, chunks[1], app);
-    crate::ui::preview::draw_preview_panel(f, chunks[2], app);
+    if chunks[2].width > 0
+    {
+        crate::ui::preview::draw_preview_panel(f, chunks[2], app);
+    }
 
     // which-key overlay (draw last so it appears on top)
     match app.overlay
@@ -81,11 +88,31 @@ pub fn draw(
         {
             panes::draw_theme_picker_panel(f, f.area(), app);
         }
+        crate::app::Overlay::TemplatePicker(_) =>
+        {
+            panes::draw_template_picker_panel(f, f.area(), app);
+        }
         crate::app::Overlay::None =>
         {}
     }
 }
 
+/// "3 selected · 12.4 MB" for the header's right side.
+fn selection_summary(app: &crate::App) -> template::HeaderSide
+{
+    let bytes = app.selection_size.total(&app.selected, &app.dir_sizes);
+    let size = match app.display_mode
+    {
+        crate::app::DisplayMode::Friendly => format::human_size(bytes),
+        crate::app::DisplayMode::Absolute => format!("{} B", bytes),
+    };
+    let text = format!("{} selected · {}", app.selected.len(), size);
+    template::HeaderSide {
+        spans: vec![ratatui::text::Span::raw(text.clone())],
+        text,
+    }
+}
+
 fn draw_header(
     f: &mut ratatui::Frame,
     area: Rect,
@@ -101,161 +128,6 @@ fn draw_header(
             .style(ratatui::style::Style::default().bg(bg));
         f.render_widget(blk, area);
     }
-    // helper removed; header rendering now lives in
-    // template::format_header_side
-    let _unused = (); // retain function body start for patching
-    #[allow(dead_code)]
-    fn render_header_side(
-        app: &crate::App,
-        tpl_opt: Option<&String>,
-    ) -> String
-    {
-        // Validate placeholders against allowed set; log unknowns
-        fn placeholders_in(s: &str) -> Vec<String>
-        {
-            let mut out = Vec::new();
-            let mut i = 0;
-            let b = s.as_bytes();
-            while i < b.len()
-            {
-                if b[i] == b'{'
-                    && let Some(j) = s[i + 1..].find('}')
-                {
-                    let end = i + 1 + j + 1;
-                    let name = &s[i + 1..end - 1];
-                    if !name.is_empty()
-                    {
-                        out.push(name.to_string());
-                    }
-                    i = end;
-                    continue;
-                }
-                let ch = s[i..].chars().next().unwrap();
-                i += ch.len_utf8();
-            }
-            out
-        }
-
-        use chrono::Local;
-        let now = Local::now();
-        let date_s = now.format("%Y-%m-%d").to_string();
-        let time_s = now.format("%H:%M").to_string();
-        let username = whoami::username().unwrap_or_default();
-        let hostname = whoami::hostname().unwrap_or_default();
-        let cwd_s = app.cwd.display().to_string();
-        let sel_opt = app.selected_entry();
-        let current_file = sel_opt
-            .as_ref()
-            .map(|e| e.path.display().to_string())
-            .unwrap_or_else(|| cwd_s.clone());
-        let owner = sel_opt
-            .as_ref()
-            .map(|e| owner_string(&e.path))
-            .unwrap_or_else(|| String::from("-"));
-        let perms = sel_opt
-            .as_ref()
-            .map(|e| crate::ui::panes::permissions_string(e))
-            .unwrap_or_else(|| String::from("---------"));
-        let size_s = sel_opt
-            .as_ref()
-            .map(|e| {
-                if e.is_dir
-                {
-                    "-".to_string()
-                }
-                else
-                {
-                    match app.display_mode
-                    {
-                        crate::app::DisplayMode::Friendly =>
-                        {
-                            crate::ui::panes::human_size(e.size)
-                        }
-                        crate::app::DisplayMode::Absolute =>
-                        {
-                            format!("{} B", e.size)
-                        }
-                    }
-                }
-            })
-            .unwrap_or_else(|| String::from("-"));
-        let ext = sel_opt
-            .as_ref()
-            .and_then(|e| {
-                e.path
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .map(|s| s.to_string())
-            })
-            .unwrap_or_default();
-        let ctime_s = sel_opt
-            .as_ref()
-            .and_then(|e| e.ctime)
-            .map(|t| {
-                let fmt = app
-                    .config
-                    .ui
-                    .date_format
-                    .as_deref()
-                    .unwrap_or("%Y-%m-%d %H:%M");
-                crate::ui::panes::format_time_abs(t, fmt)
-            })
-            .unwrap_or_else(|| String::from("-"));
-        let mtime_s = sel_opt
-            .as_ref()
-            .and_then(|e| e.mtime)
-            .map(|t| {
-                let fmt = app
-                    .config
-                    .ui
-                    .date_format
-                    .as_deref()
-                    .unwrap_or("%Y-%m-%d %H:%M");
-                crate::ui::panes::format_time_abs(t, fmt)
-            })
-            .unwrap_or_else(|| String::from("-"));
-
-        let tpl = tpl_opt.cloned().unwrap_or_default();
-
-        // Allowed placeholders for header templates
-        let allowed = [
-            "date",
-            "time",
-            "cwd",
-            "current_file",
-            "username",
-            "hostname",
-            "current_file_permissions",
-            "current_file_size",
-            "current_file_ctime",
-            "current_file_mtime",
-            "current_file_extension",
-            "owner",
-        ];
-        for ph in placeholders_in(&tpl)
-        {
-            if !allowed.iter().any(|&a| a == ph)
-            {
-                crate::trace::log(format!(
-                    "[header] unknown placeholder '{{{}}}'",
-                    ph
-                ));
-            }
-        }
-        tpl.replace("{date}", &date_s)
-            .replace("{time}", &time_s)
-            .replace("{cwd}", &cwd_s)
-            .replace("{current_file}", &current_file)
-            .replace("{username}", &username)
-            .replace("{hostname}", &hostname)
-            .replace("{current_file_permissions}", &perms)
-            .replace("{current_file_size}", &size_s)
-            .replace("{current_file_ctime}", &ctime_s)
-            .replace("{current_file_mtime}", &mtime_s)
-            .replace("{current_file_extension}", &ext)
-            .replace("{owner}", &owner)
-    }
-
     // Prefer user-configured templates; fall back to a sensible default
     let left_tpl = app.config.ui.header_left.as_ref().cloned().or_else(|| {
         Some(crate::config::defaults::DEFAULT_HEADER_LEFT.to_string())
@@ -265,8 +137,60 @@ fn draw_header(
             Some(crate::config::defaults::DEFAULT_HEADER_RIGHT.to_string())
         });
 
-    let left_side = template::format_header_side(app, left_tpl.as_ref());
-    let right_side = template::format_header_side(app, right_tpl.as_ref());
+    let mut left_side = template::format_header_side(app, left_tpl.as_ref());
+    if app.tabs.len() > 1
+    {
+        let bar = tab_bar_spans(app);
+        left_side.spans.splice(0..0, bar);
+    }
+    // With entries marked, summarise the selection instead of the entry
+    let mut right_side = if app.selected.is_empty()
+    {
+        template::format_header_side(app, right_tpl.as_ref())
+    }
+    else
+    {
+        selection_summary(app)
+    };
+    if let Some(code) = app.last_exit
+    {
+        // Last command's exit status: green on success, red on failure
+        let (glyph, color) = if code == 0
+        {
+            (format!("✓ {} ", code), ratatui::style::Color::Green)
+        }
+        else
+        {
+            (format!("✗ {} ", code), ratatui::style::Color::Red)
+        };
+        right_side.text.insert_str(0, &glyph);
+        right_side.spans.insert(
+            0,
+            ratatui::text::Span::styled(
+                glyph,
+                ratatui::style::Style::default().fg(color),
+            ),
+        );
+    }
+    if let Some(filter) = app.list_filter.as_ref()
+    {
+        let tag = format!("[{}] ", filter.pattern);
+        right_side.text.insert_str(0, &tag);
+        right_side.spans.insert(
+            0,
+            ratatui::text::Span::styled(
+                tag,
+                ratatui::style::Style::default()
+                    .fg(ratatui::style::Color::Magenta),
+            ),
+        );
+    }
+    if app.config.ui.show_spinner && app.is_busy()
+    {
+        let glyph = format!("{} ", format::spinner_frame(app.spinner_tick));
+        right_side.text.insert_str(0, &glyph);
+        right_side.spans.insert(0, ratatui::text::Span::raw(glyph));
+    }
 
     // Compute widths from plain text
     let total = area.width as usize;
@@ -289,16 +213,16 @@ fn draw_header(
         {
             let s = sp.content.as_ref();
             let mut acc = String::new();
-            for ch in s.chars()
+            for g in
+                unicode_segmentation::UnicodeSegmentation::graphemes(s, true)
             {
-                let cw =
-                    unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
-                if used + cw > max_w
+                let gw = UnicodeWidthStr::width(g);
+                if used + gw > max_w
                 {
                     break;
                 }
-                used += cw;
-                acc.push(ch);
+                used += gw;
+                acc.push_str(g);
             }
             if !acc.is_empty()
             {
@@ -323,66 +247,34 @@ fn draw_header(
         .into_iter()
         .map(|s| ratatui::text::Span::styled(s.content.into_owned(), s.style))
         .collect();
-    // Apply default fg/bg to spans where not explicitly set
-    if let Some(th) = app.config.ui.theme.as_ref()
+    // Apply default fg/bg to spans where not explicitly set: ui.header_fg/bg
+    // win over the theme's title colors
+    let theme = app.config.ui.theme.as_ref();
+    let fg_opt = app
+        .config
+        .ui
+        .header_fg
+        .as_ref()
+        .or_else(|| theme.and_then(|t| t.title_fg.as_ref()))
+        .and_then(|s| crate::ui::colors::parse_color(s));
+    let bg_opt = app
+        .config
+        .ui
+        .header_bg
+        .as_ref()
+        .or_else(|| theme.and_then(|t| t.title_bg.as_ref()))
+        .and_then(|s| crate::ui::colors::parse_color(s));
+    for sp in left_spans_final.iter_mut().chain(right_spans_final.iter_mut())
     {
-        // Prefer explicit ui.header_fg if provided, else theme title_fg
-        let fg_opt = app
-            .config
-            .ui
-            .header_fg
-            .as_ref()
-            .and_then(|s| crate::ui::colors::parse_color(s))
-            .or_else(|| {
-                th.title_fg
-                    .as_ref()
-                    .and_then(|s| crate::ui::colors::parse_color(s))
-            });
         if let Some(fg) = fg_opt
+            && sp.style.fg.is_none()
         {
-            for sp in &mut left_spans_final
-            {
-                if sp.style.fg.is_none()
-                {
-                    sp.style = sp.style.fg(fg);
-                }
-            }
-            for sp in &mut right_spans_final
-            {
-                if sp.style.fg.is_none()
-                {
-                    sp.style = sp.style.fg(fg);
-                }
-            }
+            sp.style = sp.style.fg(fg);
         }
-        // Prefer explicit ui.header_bg if provided, else theme title_bg
-        let bg_opt = app
-            .config
-            .ui
-            .header_bg
-            .as_ref()
-            .and_then(|s| crate::ui::colors::parse_color(s))
-            .or_else(|| {
-                th.title_bg
-                    .as_ref()
-                    .and_then(|s| crate::ui::colors::parse_color(s))
-            });
         if let Some(bg) = bg_opt
+            && sp.style.bg.is_none()
         {
-            for sp in &mut left_spans_final
-            {
-                if sp.style.bg.is_none()
-                {
-                    sp.style = sp.style.bg(bg);
-                }
-            }
-            for sp in &mut right_spans_final
-            {
-                if sp.style.bg.is_none()
-                {
-                    sp.style = sp.style.bg(bg);
-                }
-            }
+            sp.style = sp.style.bg(bg);
         }
     }
 
@@ -395,6 +287,29 @@ fn draw_header(
     f.render_widget(right_p, area);
 }
 
+/// Tab bar shown at the start of the header when more than one tab is open;
+/// the active tab is reversed.
+fn tab_bar_spans(app: &crate::App) -> Vec<ratatui::text::Span<'static>>
+{
+    let mut spans = Vec::new();
+    for (i, label) in app.tab_labels().into_iter().enumerate()
+    {
+        let text = format!(" {}:{} ", i + 1, label);
+        let style = if i == app.active_tab
+        {
+            ratatui::style::Style::default()
+                .add_modifier(ratatui::style::Modifier::REVERSED)
+        }
+        else
+        {
+            ratatui::style::Style::default()
+        };
+        spans.push(ratatui::text::Span::styled(text, style));
+    }
+    spans.push(ratatui::text::Span::raw(" "));
+    spans
+}
+
 #[cfg(unix)]
 fn owner_string(path: &std::path::Path) -> String
 {
@@ -419,116 +334,74 @@ fn owner_string(_path: &std::path::Path) -> String
     String::from("-")
 }
 
+/// id -> name table parsed from /etc/passwd or /etc/group. `None` values
+/// record ids that could not be resolved so they are not retried.
 #[cfg(unix)]
-static UID_CACHE: OnceLock<RwLock<HashMap<u32, String>>> = OnceLock::new();
-#[cfg(unix)]
-static GID_CACHE: OnceLock<RwLock<HashMap<u32, String>>> = OnceLock::new();
+type NameMap = HashMap<u32, Option<String>>;
 
 #[cfg(unix)]
-fn uid_cache() -> &'static RwLock<HashMap<u32, String>>
-{
-    UID_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
-}
+static UID_CACHE: OnceLock<RwLock<Option<NameMap>>> = OnceLock::new();
 #[cfg(unix)]
-fn gid_cache() -> &'static RwLock<HashMap<u32, String>>
-{
-    GID_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
-}
+static GID_CACHE: OnceLock<RwLock<Option<NameMap>>> = OnceLock::new();
 
+/// Parse a colon-separated database (`name:pw:id:...`) into an id -> name map.
 #[cfg(unix)]
-fn lookup_user_name(uid: u32) -> Option<String>
+fn parse_id_database(path: &str) -> NameMap
 {
-    // Fast path: check cache
-    if let Ok(map) = uid_cache().read()
-        && let Some(v) = map.get(&uid)
-    {
-        return Some(v.clone());
-    }
-    // Parse /etc/passwd to resolve uid -> name
-    let found = if let Ok(text) = std::fs::read_to_string("/etc/passwd")
+    let mut map = NameMap::new();
+    if let Ok(text) = std::fs::read_to_string(path)
     {
-        text.lines().find_map(|line| {
+        for line in text.lines()
+        {
             if line.trim().is_empty() || line.starts_with('#')
             {
-                return None;
+                continue;
             }
             let mut parts = line.split(':');
-            let name = parts.next()?;
-            let _pw = parts.next();
-            let uid_str = parts.next()?;
-            if uid_str.parse::<u32>().ok()? == uid
-            {
-                Some(name.to_string())
-            }
+            let (Some(name), _, Some(id_str)) =
+                (parts.next(), parts.next(), parts.next())
             else
             {
-                None
-            }
-        })
-    }
-    else
-    {
-        None
-    }
-    // Fallback: try `id -nu <uid>` on Unix systems where /etc/passwd is not
-    // authoritative (e.g., macOS)
-    .or_else(|| {
-        use std::process::Command;
-        let out = Command::new("id").arg("-nu").arg(uid.to_string()).output();
-        match out
-        {
-            Ok(o) if o.status.success() =>
+                continue;
+            };
+            if let Ok(id) = id_str.parse::<u32>()
             {
-                let s = String::from_utf8_lossy(&o.stdout).trim().to_string();
-                if s.is_empty() { None } else { Some(s) }
+                // First entry wins, matching getpwuid/getgrgid semantics
+                map.entry(id).or_insert_with(|| Some(name.to_string()));
             }
-            _ => None,
         }
-    });
-    if let Some(ref name) = found
-        && let Ok(mut map) = uid_cache().write()
-    {
-        map.insert(uid, name.clone());
     }
-    found
+    map
 }
 
+/// Resolve an id through `cache`, parsing `db_path` once on first use and
+/// falling back to `id <flag> <id>` where the database is not authoritative
+/// (e.g., macOS).
 #[cfg(unix)]
-fn lookup_group_name(gid: u32) -> Option<String>
+fn lookup_cached_name(
+    cache: &'static OnceLock<RwLock<Option<NameMap>>>,
+    db_path: &str,
+    id_flag: &str,
+    id: u32,
+) -> Option<String>
 {
-    if let Ok(map) = gid_cache().read()
-        && let Some(v) = map.get(&gid)
-    {
-        return Some(v.clone());
-    }
-    let found = if let Ok(text) = std::fs::read_to_string("/etc/group")
+    let lock = cache.get_or_init(|| RwLock::new(None));
+    // Fast path: table loaded and id known
+    if let Ok(guard) = lock.read()
+        && let Some(map) = guard.as_ref()
+        && let Some(v) = map.get(&id)
     {
-        text.lines().find_map(|line| {
-            if line.trim().is_empty() || line.starts_with('#')
-            {
-                return None;
-            }
-            let mut parts = line.split(':');
-            let name = parts.next()?;
-            let _pw = parts.next();
-            let gid_str = parts.next()?;
-            if gid_str.parse::<u32>().ok()? == gid
-            {
-                Some(name.to_string())
-            }
-            else
-            {
-                None
-            }
-        })
+        return v.clone();
     }
-    else
+    let mut guard = lock.write().ok()?;
+    let map = guard.get_or_insert_with(|| parse_id_database(db_path));
+    if let Some(v) = map.get(&id)
     {
-        None
+        return v.clone();
     }
-    .or_else(|| {
+    let found = {
         use std::process::Command;
-        let out = Command::new("id").arg("-ng").arg(gid.to_string()).output();
+        let out = Command::new("id").arg(id_flag).arg(id.to_string()).output();
         match out
         {
             Ok(o) if o.status.success() =>
@@ -538,27 +411,34 @@ fn lookup_group_name(gid: u32) -> Option<String>
             }
             _ => None,
         }
-    });
-    if let Some(ref name) = found
-        && let Ok(mut map) = gid_cache().write()
-    {
-        map.insert(gid, name.clone());
-    }
+    };
+    map.insert(id, found.clone());
     found
 }
 
+#[cfg(unix)]
+fn lookup_user_name(uid: u32) -> Option<String>
+{
+    lookup_cached_name(&UID_CACHE, "/etc/passwd", "-nu", uid)
+}
+
+#[cfg(unix)]
+fn lookup_group_name(gid: u32) -> Option<String>
+{
+    lookup_cached_name(&GID_CACHE, "/etc/group", "-ng", gid)
+}
+
+/// Drop the parsed user/group tables so the next lookup re-reads them.
 #[cfg(unix)]
 pub fn clear_owner_cache()
 {
-    if let Some(lock) = UID_CACHE.get()
-        && let Ok(mut m) = lock.write()
+    for cache in [&UID_CACHE, &GID_CACHE]
     {
-        m.clear();
-    }
-    if let Some(lock) = GID_CACHE.get()
-        && let Ok(mut m) = lock.write()
-    {
-        m.clear();
+        if let Some(lock) = cache.get()
+            && let Ok(mut m) = lock.write()
+        {
+            *m = None;
+        }
     }
 }
 
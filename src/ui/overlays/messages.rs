@@ -23,7 +23,7 @@ use ratatui::{
 pub fn draw_messages_panel(
     f: &mut ratatui::Frame,
     area: Rect,
-    app: &crate::App,
+    app: &mut crate::App,
 )
 {
     let min_h = ((area.height as u32 * 20) / 100).max(3) as u16;
@@ -31,8 +31,24 @@ pub fn draw_messages_panel(
     let needed = (app.recent_messages.len() as u16).saturating_add(2).max(3);
     let panel_h = needed.min(max_h).max(min_h).min(area.height);
 
+    // Newest at the bottom; `messages_scroll` rows are hidden below the view
+    let avail_rows = panel_h.saturating_sub(2) as usize;
+    let total = app.recent_messages.len();
+    app.messages_scroll =
+        app.messages_scroll.min(total.saturating_sub(avail_rows));
+    let end = total - app.messages_scroll;
+    let start = end.saturating_sub(avail_rows);
+    let title = if start > 0
+    {
+        format!("Messages ({} more)", start)
+    }
+    else
+    {
+        "Messages".to_string()
+    };
+
     let mut block = Block::default().borders(Borders::ALL).title(Span::styled(
-        "Messages",
+        title,
         Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
     ));
     if let Some(th) = app.config.ui.theme.as_ref()
@@ -57,9 +73,7 @@ pub fn draw_messages_panel(
     let panel = layout[1];
     f.render_widget(Clear, panel);
 
-    let avail_rows = panel_h.saturating_sub(2) as usize;
-    let start = app.recent_messages.len().saturating_sub(avail_rows);
-    let slice = &app.recent_messages[start..];
+    let slice = &app.recent_messages[start..end];
     let mut lines: Vec<ratatui::text::Line> = Vec::new();
     for m in slice
     {
@@ -3,6 +3,7 @@ pub mod confirm;
 pub mod messages;
 pub mod output;
 pub mod prompt;
+pub mod template_picker;
 pub mod theme_picker;
 pub mod whichkey;
 
@@ -11,5 +12,6 @@ pub use confirm::draw_confirm_panel;
 pub use messages::draw_messages_panel;
 pub use output::draw_output_panel;
 pub use prompt::draw_prompt_panel;
+pub use template_picker::draw_template_picker_panel;
 pub use theme_picker::draw_theme_picker_panel;
 pub use whichkey::draw_whichkey_panel;
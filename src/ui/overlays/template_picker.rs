@@ -0,0 +1,32 @@
+use ratatui::layout::Rect;
+
+pub fn draw_template_picker_panel(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    app: &crate::App,
+)
+{
+    let state = match app.overlay
+    {
+        crate::app::Overlay::TemplatePicker(ref s) => s.as_ref(),
+        _ => return,
+    };
+    let names: Vec<String> = state
+        .entries
+        .iter()
+        .map(|p| {
+            p.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| p.display().to_string())
+        })
+        .collect();
+    super::theme_picker::draw_picker(
+        f,
+        area,
+        app,
+        "New File From Template",
+        &names,
+        state.selected,
+        "↑/↓ select  Enter choose  Esc cancel",
+    );
+}
@@ -34,15 +34,39 @@ pub fn draw_theme_picker_panel(
         crate::app::Overlay::ThemePicker(ref s) => s.as_ref(),
         _ => return,
     };
-    if state.entries.is_empty()
+    let names: Vec<String> =
+        state.entries.iter().map(|e| e.name.clone()).collect();
+    draw_picker(
+        f,
+        area,
+        app,
+        "Select UI Theme",
+        &names,
+        state.selected,
+        "↑/↓ preview  Enter apply  Esc cancel",
+    );
+}
+
+/// Centered list popup shared by the pickers: `names` with `selected`
+/// highlighted, sized by `ui.modals.theme` when set, and `hint` underneath.
+pub(crate) fn draw_picker(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    app: &crate::App,
+    title: &str,
+    names: &[String],
+    selected: usize,
+    hint: &str,
+)
+{
+    if names.is_empty()
     {
         return;
     }
 
-    let max_name_width = state
-        .entries
+    let max_name_width = names
         .iter()
-        .map(|e| UnicodeWidthStr::width(e.name.as_str()))
+        .map(|n| UnicodeWidthStr::width(n.as_str()))
         .max()
         .unwrap_or(0);
     let (popup_width, popup_height) = if let Some(m) =
@@ -64,7 +88,7 @@ pub fn draw_theme_picker_panel(
             .min(area.width.saturating_sub(4).max(20))
             .min(area.width)
             .max(10);
-        let entries_len = state.entries.len() as u16;
+        let entries_len = names.len() as u16;
         let desired_height = entries_len.saturating_add(4);
         let h = desired_height
             .min(area.height.saturating_sub(4).max(6))
@@ -120,7 +144,7 @@ pub fn draw_theme_picker_panel(
     {
         title_style = title_style.bg(tb);
     }
-    block = block.title(Span::styled("Select UI Theme", title_style));
+    block = block.title(Span::styled(title.to_string(), title_style));
 
     let inner = block.inner(popup);
     f.render_widget(block, popup);
@@ -163,12 +187,9 @@ pub fn draw_theme_picker_panel(
         }
     }
 
-    let items: Vec<ListItem> = state
-        .entries
+    let items: Vec<ListItem> = names
         .iter()
-        .map(|entry| {
-            ListItem::new(ratatui::text::Line::from(entry.name.clone()))
-        })
+        .map(|name| ListItem::new(ratatui::text::Line::from(name.clone())))
         .collect();
 
     let constraints: Vec<Constraint> = if inner.height > 3
@@ -186,7 +207,7 @@ pub fn draw_theme_picker_panel(
     let list_area = chunks[0];
 
     let mut list_state = ListState::default();
-    list_state.select(Some(state.selected));
+    list_state.select(Some(selected));
     let list = List::new(items).style(base_style).highlight_style(highlight);
     f.render_stateful_widget(list, list_area, &mut list_state);
 
@@ -202,7 +223,7 @@ pub fn draw_theme_picker_panel(
         {
             info_style = info_style.fg(fg);
         }
-        let hint = Paragraph::new("↑/↓ preview  Enter apply  Esc cancel")
+        let hint = Paragraph::new(hint.to_string())
             .style(info_style)
             .alignment(ratatui::layout::Alignment::Center);
         f.render_widget(hint, info_area);
@@ -29,27 +29,7 @@ pub fn draw_whichkey_panel(
     app: &crate::App,
 )
 {
-    fn tokenize_seq(s: &str) -> Vec<String>
-    {
-        let mut out = Vec::new();
-        let mut i = 0;
-        let b = s.as_bytes();
-        while i < b.len()
-        {
-            if b[i] == b'<'
-                && let Some(j) = s[i + 1..].find('>')
-            {
-                let end = i + 1 + j + 1;
-                out.push(s[i..end].to_string());
-                i = end;
-                continue;
-            }
-            let ch = s[i..].chars().next().unwrap();
-            out.push(ch.to_string());
-            i += ch.len_utf8();
-        }
-        out
-    }
+    use crate::keymap::tokenize_sequence as tokenize_seq;
 
     fn format_token(tok: &str) -> String
     {
@@ -162,7 +142,11 @@ pub fn draw_whichkey_panel(
         else
         {
             let n = list.len();
-            let label = if n == 1
+            let label = if let Some(name) = app.config.keys.groups.get(&k)
+            {
+                name.clone()
+            }
+            else if n == 1
             {
                 "(1 binding)".to_string()
             }
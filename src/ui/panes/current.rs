@@ -6,8 +6,6 @@ use ratatui::{
         Style,
     },
     widgets::{
-        Block,
-        Borders,
         Clear,
         List,
         ListItem,
@@ -21,7 +19,7 @@ pub fn draw_current_panel(
 )
 {
     f.render_widget(Clear, area);
-    let mut block = Block::default().borders(Borders::ALL);
+    let mut block = super::layout::pane_block(app);
     if let Some(th) = app.config.ui.theme.as_ref()
     {
         if let Some(bg) =
@@ -39,17 +37,37 @@ pub fn draw_current_panel(
     }
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
+    app.visible_rows = inner.height as usize;
     let fmt = app.config.ui.row.clone().unwrap_or_default();
+    // Rows in a pending visual range show the selection bar live
+    let visual = app.visual_range();
+    let gutter_w =
+        crate::ui::row::line_number_width(app, app.current_entries.len());
+    let gutter_w = if gutter_w < inner.width { gutter_w } else { 0 };
     let items: Vec<ListItem> = app
         .current_entries
         .iter()
-        .map(|e| {
-            ListItem::new(crate::ui::row::build_row_line(
+        .enumerate()
+        .map(|(i, e)| {
+            let mut line = crate::ui::row::build_row_line(
                 app,
                 &fmt,
                 e,
-                inner.width,
-            ))
+                inner.width - gutter_w,
+            );
+            if visual.as_ref().is_some_and(|r| r.contains(&i))
+                && let Some(bar) = line.spans.first_mut()
+            {
+                bar.content = "┃".into();
+            }
+            if gutter_w > 0
+            {
+                line.spans.insert(
+                    0,
+                    crate::ui::row::line_number_span(app, i, gutter_w),
+                );
+            }
+            ListItem::new(line)
         })
         .collect();
 
@@ -77,6 +95,10 @@ pub fn draw_current_panel(
         {
             hl = hl.bg(bg);
         }
+        if let Some(names) = th.selected_item_mods.as_deref()
+        {
+            hl = hl.add_modifier(crate::ui::colors::parse_modifiers(names));
+        }
         list = list.highlight_style(hl.add_modifier(Modifier::BOLD));
         if let Some(fg) =
             th.item_fg.as_ref().and_then(|s| crate::ui::colors::parse_color(s))
@@ -96,5 +118,18 @@ pub fn draw_current_panel(
         );
     }
 
+    let margin = app.config.ui.scrolloff;
+    if margin > 0
+        && let Some(sel) = app.list_state.selected()
+    {
+        let off = super::scrolloff_offset(
+            app.list_state.offset(),
+            sel,
+            app.current_entries.len(),
+            list_area.height as usize,
+            margin,
+        );
+        *app.list_state.offset_mut() = off;
+    }
     f.render_stateful_widget(list, list_area, &mut app.list_state);
 }
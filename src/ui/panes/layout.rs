@@ -1,4 +1,11 @@
-use ratatui::layout::Constraint;
+use ratatui::{
+    layout::Constraint,
+    widgets::{
+        Block,
+        BorderType,
+        Borders,
+    },
+};
 
 pub fn pane_constraints(app: &crate::App) -> [Constraint; 3]
 {
@@ -18,6 +25,17 @@ pub fn pane_constraints(app: &crate::App) -> [Constraint; 3]
             Constraint::Percentage(30),
         ];
     }
+    if r == 0
+    {
+        // A hidden preview takes no columns; a percentage would round up to
+        // a bordered sliver
+        let p_norm = (p as u32 * 100 / (p + c).max(1) as u32) as u16;
+        return [
+            Constraint::Percentage(p_norm),
+            Constraint::Fill(1),
+            Constraint::Length(0),
+        ];
+    }
     let p_norm = (p as u32 * 100 / total as u32) as u16;
     let c_norm = (c as u32 * 100 / total as u32) as u16;
     let r_norm = 100u16.saturating_sub(p_norm).saturating_sub(c_norm);
@@ -27,3 +45,47 @@ pub fn pane_constraints(app: &crate::App) -> [Constraint; 3]
         Constraint::Percentage(r_norm),
     ]
 }
+
+/// List offset that keeps `margin` rows visible above and below `selected`
+/// (`ui.scrolloff`), moving `offset` as little as possible. The margin is
+/// reduced when the viewport is too short to honor it on both sides.
+pub fn scrolloff_offset(
+    offset: usize,
+    selected: usize,
+    len: usize,
+    height: usize,
+    margin: usize,
+) -> usize
+{
+    if height == 0 || len <= height
+    {
+        return 0;
+    }
+    let margin = margin.min(height.saturating_sub(1) / 2);
+    let mut off = offset;
+    if selected < off + margin
+    {
+        off = selected.saturating_sub(margin);
+    }
+    if selected + margin >= off + height
+    {
+        off = selected + margin + 1 - height;
+    }
+    off.min(len - height)
+}
+
+/// Base block for the parent, current and preview panes honoring
+/// `ui.border_style` (`plain`, `rounded`, `thick`, `double` or `none`).
+pub fn pane_block(app: &crate::App) -> Block<'static>
+{
+    let style = app.config.ui.border_style.as_deref().unwrap_or("plain");
+    let kind = match style.to_ascii_lowercase().as_str()
+    {
+        "none" => return Block::default().borders(Borders::NONE),
+        "rounded" => BorderType::Rounded,
+        "thick" => BorderType::Thick,
+        "double" => BorderType::Double,
+        _ => BorderType::Plain,
+    };
+    Block::default().borders(Borders::ALL).border_type(kind)
+}
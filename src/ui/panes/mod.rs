@@ -9,12 +9,13 @@ pub use super::{
         draw_messages_panel,
         draw_output_panel,
         draw_prompt_panel,
+        draw_template_picker_panel,
         draw_theme_picker_panel,
         draw_whichkey_panel,
     },
     row::{
         build_row_line,
-        permissions_string,
+        permissions_display,
     },
 };
 mod current;
@@ -22,6 +23,10 @@ mod layout;
 mod parent;
 pub use self::{
     current::draw_current_panel,
-    layout::pane_constraints,
+    layout::{
+        pane_block,
+        pane_constraints,
+        scrolloff_offset,
+    },
     parent::draw_parent_panel,
 };
@@ -1,9 +1,10 @@
 use ratatui::{
     layout::Rect,
-    style::Style,
+    style::{
+        Modifier,
+        Style,
+    },
     widgets::{
-        Block,
-        Borders,
         Clear,
         List,
         ListItem,
@@ -13,11 +14,11 @@ use ratatui::{
 pub fn draw_parent_panel(
     f: &mut ratatui::Frame,
     area: Rect,
-    app: &crate::App,
+    app: &mut crate::App,
 )
 {
     f.render_widget(Clear, area);
-    let mut block = Block::default().borders(Borders::ALL);
+    let mut block = super::layout::pane_block(app);
     if let Some(th) = app.config.ui.theme.as_ref()
     {
         if let Some(bg) =
@@ -69,5 +70,37 @@ pub fn draw_parent_panel(
             list = list.style(Style::default().bg(bg));
         }
     }
-    f.render_widget(list, list_area);
+    // The parent cursor (the cwd's entry until moved) is selected so it stays
+    // scrolled into view; with ui.preview_follows = "parent" it also takes
+    // the selection colours
+    let mut hl = Style::default().add_modifier(Modifier::BOLD);
+    if app.preview_follows_parent()
+        && let Some(th) = app.config.ui.theme.as_ref()
+    {
+        if let Some(fg) = th
+            .selected_item_fg
+            .as_ref()
+            .and_then(|s| crate::ui::colors::parse_color(s))
+        {
+            hl = hl.fg(fg);
+        }
+        if let Some(bg) = th
+            .selected_item_bg
+            .as_ref()
+            .and_then(|s| crate::ui::colors::parse_color(s))
+        {
+            hl = hl.bg(bg);
+        }
+        if let Some(names) = th.selected_item_mods.as_deref()
+        {
+            hl = hl.add_modifier(crate::ui::colors::parse_modifiers(names));
+        }
+    }
+    let idx = app.parent_selected_index();
+    app.parent_list_state.select(idx);
+    f.render_stateful_widget(
+        list.highlight_style(hl),
+        list_area,
+        &mut app.parent_list_state,
+    );
 }
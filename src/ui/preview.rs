@@ -7,6 +7,7 @@ use ratatui::{
     layout::Rect,
     style::{
         Color,
+        Modifier,
         Style,
     },
     text::{
@@ -15,7 +16,6 @@ use ratatui::{
     },
     widgets::{
         Block,
-        Borders,
         Clear,
         Paragraph,
         Wrap,
@@ -24,8 +24,11 @@ use ratatui::{
 
 use crate::ui::ansi::ansi_spans;
 use mlua::Value as LuaValue;
+use unicode_width::UnicodeWidthChar;
 
 const PREVIEW_LINES_LIMIT: usize = 1000;
+// Levels below the selected directory shown with ui.dir_preview = "tree"
+const DIR_PREVIEW_TREE_DEPTH: usize = 2;
 
 pub fn draw_preview_panel(
     f: &mut ratatui::Frame,
@@ -34,27 +37,76 @@ pub fn draw_preview_panel(
 )
 {
     f.render_widget(Clear, area);
-    let block = preview_block(app);
+    let mut block = preview_block(app);
+    if app.config.ui.show_selection_path
+        && let Some(e) = app.selected_entry()
+    {
+        let text = crate::ui::row::truncate_path_left(
+            &e.path,
+            area.width.saturating_sub(2) as usize,
+        );
+        let mut style = Style::default();
+        if let Some(fg) = app
+            .config
+            .ui
+            .theme
+            .as_ref()
+            .and_then(|th| th.title_fg.as_ref())
+            .and_then(|s| crate::ui::colors::parse_color(s))
+        {
+            style = style.fg(fg);
+        }
+        block = block.title(Span::styled(text, style));
+    }
     let preview_area = block.inner(area);
+    if app.preview_pending_since.is_some()
+    {
+        // Selection still settling (ui.preview_debounce_ms): skip previewers
+        f.render_widget(block, area);
+        return;
+    }
     let mut dynamic_lines: Option<Vec<String>> = None;
-    if let Some(sel) = app.selected_entry()
+    // With ui.preview_keep_file a selected directory keeps showing the last
+    // file, so previewers run for that file instead
+    let file_target = app.preview_file_target().map(|(p, _)| p);
+    if app.preview_entry().is_some()
     {
-        if !sel.is_dir
+        if let Some(path) = file_target.as_ref()
         {
-            let key =
-                (sel.path.clone(), preview_area.width, preview_area.height);
+            let key = (path.clone(), preview_area.width, preview_area.height);
             if app.preview.cache_key.as_ref() == Some(&key)
             {
                 dynamic_lines = app.preview.cache_lines.clone();
             }
             else
             {
-                dynamic_lines = run_previewer(
-                    app,
-                    &sel.path,
-                    preview_area,
+                dynamic_lines =
+                    run_previewer(app, path, preview_area, PREVIEW_LINES_LIMIT);
+                app.preview.cache_key = Some(key);
+                app.preview.cache_lines = dynamic_lines.clone();
+            }
+        }
+        else if app.dir_preview_tree()
+            && let Some(dir) = app.preview_entry().map(|e| e.path.clone())
+        {
+            // The walk is cached like previewer output, keyed by the
+            // directory and pane size
+            let key = (dir.clone(), preview_area.width, preview_area.height);
+            if app.preview.cache_key.as_ref() == Some(&key)
+            {
+                dynamic_lines = app.preview.cache_lines.clone();
+            }
+            else
+            {
+                let text = crate::core::tree::tree_text(
+                    &dir,
+                    DIR_PREVIEW_TREE_DEPTH,
+                    app.config.ui.show_hidden,
                     PREVIEW_LINES_LIMIT,
                 );
+                dynamic_lines = Some(
+                    text.lines().map(crate::util::sanitize_line).collect(),
+                );
                 app.preview.cache_key = Some(key);
                 app.preview.cache_lines = dynamic_lines.clone();
             }
@@ -65,9 +117,18 @@ pub fn draw_preview_panel(
             app.preview.cache_lines = None;
         }
     }
-    let text: Vec<Line> = if let Some(sel) = app.selected_entry()
+    // Line numbers apply to file content only, never directory listings
+    let mut numbered = false;
+    let text: Vec<Line> = if let Some(sel) = app.preview_entry()
     {
         if sel.is_dir
+            && file_target.is_none()
+            && let Some(lines) = dynamic_lines.as_ref()
+        {
+            // ui.dir_preview = "tree"
+            lines.iter().map(|l| Line::from(ansi_spans(l))).collect()
+        }
+        else if sel.is_dir && file_target.is_none()
         {
             let block_inner = block.inner(area);
             let inner_w = block_inner.width;
@@ -92,6 +153,7 @@ pub fn draw_preview_panel(
             }
             else
             {
+                numbered = app.config.ui.preview_line_numbers;
                 lines.iter().map(|l| Line::from(ansi_spans(l))).collect()
             }
         }
@@ -104,6 +166,7 @@ pub fn draw_preview_panel(
         }
         else
         {
+            numbered = app.config.ui.preview_line_numbers;
             app.preview
                 .static_lines
                 .iter()
@@ -127,10 +190,9 @@ pub fn draw_preview_panel(
             .collect()
     };
 
-    let mut para = Paragraph::new(text).block(block).wrap(Wrap { trim: true });
+    let mut st = Style::default();
     if let Some(th) = app.config.ui.theme.as_ref()
     {
-        let mut st = Style::default();
         if let Some(fg) =
             th.item_fg.as_ref().and_then(|s| crate::ui::colors::parse_color(s))
         {
@@ -141,14 +203,119 @@ pub fn draw_preview_panel(
         {
             st = st.bg(bg);
         }
-        para = para.style(st);
+    }
+
+    let digits = text.len().max(1).to_string().len() as u16;
+    let gutter_w = digits + 1;
+    if numbered && preview_area.width > gutter_w
+    {
+        // Hard-wrap the content next to a separate gutter so the numbers
+        // do not eat into the wrapping width and stay aligned.
+        let content_w = preview_area.width - gutter_w;
+        let (gutter, body) = number_lines(
+            text,
+            digits,
+            content_w,
+            preview_area.height,
+            app.preview_wrap,
+        );
+        f.render_widget(block, area);
+        let gutter_rect = Rect { width: gutter_w, ..preview_area };
+        let body_rect = Rect {
+            x: preview_area.x + gutter_w,
+            width: content_w,
+            ..preview_area
+        };
+        let dim = st.fg(Color::DarkGray).add_modifier(Modifier::DIM);
+        f.render_widget(Paragraph::new(gutter).style(dim), gutter_rect);
+        f.render_widget(Paragraph::new(body).style(st), body_rect);
+        return;
+    }
+
+    let mut para = Paragraph::new(text).block(block).style(st);
+    if app.preview_wrap
+    {
+        para = para.wrap(Wrap { trim: true });
     }
     f.render_widget(para, area);
 }
 
+/// Split `text` into rows of at most `width` columns, returning the gutter
+/// rows (right-aligned 1-based numbers on each line's first row, blank on
+/// continuation rows) alongside the content rows. With `wrap` off, overflow
+/// is clipped instead. Stops after `max_rows`.
+fn number_lines(
+    text: Vec<Line<'_>>,
+    digits: u16,
+    width: u16,
+    max_rows: u16,
+    wrap: bool,
+) -> (Vec<Line<'static>>, Vec<Line<'static>>)
+{
+    let digits = digits as usize;
+    let width = width as usize;
+    let mut gutter: Vec<Line<'static>> = Vec::new();
+    let mut body: Vec<Line<'static>> = Vec::new();
+    for (idx, line) in text.into_iter().enumerate()
+    {
+        let mut rows: Vec<Vec<Span<'static>>> = vec![Vec::new()];
+        let mut col = 0usize;
+        for span in line.spans
+        {
+            let mut buf = String::new();
+            for ch in span.content.chars()
+            {
+                let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+                if col + w > width && !wrap
+                {
+                    break;
+                }
+                if col + w > width && col > 0
+                {
+                    if !buf.is_empty()
+                        && let Some(row) = rows.last_mut()
+                    {
+                        row.push(Span::styled(
+                            std::mem::take(&mut buf),
+                            span.style,
+                        ));
+                    }
+                    rows.push(Vec::new());
+                    col = 0;
+                }
+                buf.push(ch);
+                col += w;
+            }
+            if !buf.is_empty()
+                && let Some(row) = rows.last_mut()
+            {
+                row.push(Span::styled(buf, span.style));
+            }
+        }
+        for (r, spans) in rows.into_iter().enumerate()
+        {
+            let num = if r == 0
+            {
+                format!("{:>w$} ", idx + 1, w = digits)
+            }
+            else
+            {
+                " ".repeat(digits + 1)
+            };
+            gutter.push(Line::from(num));
+            body.push(Line::from(spans));
+            if body.len() >= max_rows as usize
+            {
+                return (gutter, body);
+            }
+        }
+    }
+    (gutter, body)
+}
+
 fn preview_block(app: &crate::App) -> Block<'static>
 {
-    let mut block = Block::default().borders(Borders::ALL);
+    let mut block = crate::ui::panes::pane_block(app);
     if let Some(th) = app.config.ui.theme.as_ref()
     {
         if let Some(bg) =
@@ -264,9 +431,55 @@ fn run_previewer(
             }
         }
     }
+    if let Some(template) = declarative_previewer(&app.config, path)
+    {
+        let dir_str = path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_string_lossy()
+            .to_string();
+        let cmd = crate::util::expand_command_template(
+            template,
+            path,
+            &[
+                ("width", area.width.to_string()),
+                ("height", area.height.to_string()),
+            ],
+        );
+        crate::trace::log(format!(
+            "[preview] declarative cmd='{}' file='{}'",
+            cmd,
+            path.display()
+        ));
+        return run_previewer_command(
+            &cmd,
+            &dir_str,
+            &path.to_string_lossy(),
+            limit,
+        );
+    }
     None
 }
 
+/// Command from the `previewers` table for `path`, by lowercased extension
+/// with `default` (or `*`) as the fallback.
+fn declarative_previewer<'a>(
+    config: &'a crate::config::Config,
+    path: &Path,
+) -> Option<&'a str>
+{
+    let ext = path
+        .extension()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    config
+        .previewers
+        .get(&ext)
+        .or_else(|| config.previewers.get("default"))
+        .or_else(|| config.previewers.get("*"))
+        .map(String::as_str)
+}
+
 fn run_previewer_command(
     cmd: &str,
     dir_str: &str,
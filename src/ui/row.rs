@@ -8,8 +8,86 @@ use ratatui::{
         Span,
     },
 };
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
+/// Width of the `ui.line_numbers` gutter for a listing of `count` entries
+/// (digits plus a separating space), or 0 when line numbers are off.
+pub fn line_number_width(
+    app: &crate::App,
+    count: usize,
+) -> u16
+{
+    if app.config.ui.line_numbers.is_none()
+    {
+        return 0;
+    }
+    count.max(1).to_string().len() as u16 + 1
+}
+
+/// Gutter span for row `idx`: its 1-based index, or with `"relative"` the
+/// distance from the cursor (the cursor row keeps its own index).
+pub fn line_number_span(
+    app: &crate::App,
+    idx: usize,
+    width: u16,
+) -> Span<'static>
+{
+    let cursor = app.list_state.selected();
+    let n = match app.config.ui.line_numbers.as_deref()
+    {
+        Some("relative") if cursor.is_some_and(|c| c != idx) =>
+        {
+            cursor.unwrap_or(0).abs_diff(idx)
+        }
+        _ => idx + 1,
+    };
+    let digits = (width as usize).saturating_sub(1);
+    Span::styled(
+        format!("{:>w$} ", n, w = digits),
+        Style::default().fg(Color::DarkGray),
+    )
+}
+
+/// Indicator character and style for an entry in the clipboard: the theme's
+/// `clipboard_*_glyph` (first character, default `┃`) in its
+/// `selection_bar_*_fg`/`_bg` colours (default green for copy, yellow for
+/// move).
+fn clipboard_indicator(
+    app: &crate::App,
+    op: crate::app::ClipboardOp,
+) -> (String, Style)
+{
+    let th = app.config.ui.theme.as_ref();
+    let (glyph, fg, bg, default_fg) = match op
+    {
+        crate::app::ClipboardOp::Copy => (
+            th.and_then(|t| t.clipboard_copy_glyph.as_ref()),
+            th.and_then(|t| t.selection_bar_copy_fg.as_ref()),
+            th.and_then(|t| t.selection_bar_copy_bg.as_ref()),
+            Color::Green,
+        ),
+        crate::app::ClipboardOp::Move => (
+            th.and_then(|t| t.clipboard_move_glyph.as_ref()),
+            th.and_then(|t| t.selection_bar_move_fg.as_ref()),
+            th.and_then(|t| t.selection_bar_move_bg.as_ref()),
+            Color::Yellow,
+        ),
+    };
+    let glyph = glyph
+        .and_then(|g| g.chars().next())
+        .map(String::from)
+        .unwrap_or_else(|| "┃".to_string());
+    let mut style = Style::default().fg(fg
+        .and_then(|s| crate::ui::colors::parse_color(s))
+        .unwrap_or(default_fg));
+    if let Some(bg) = bg.and_then(|s| crate::ui::colors::parse_color(s))
+    {
+        style = style.bg(bg);
+    }
+    (glyph, style)
+}
+
 pub fn build_row_line(
     app: &crate::App,
     _fmt: &crate::config::UiRowFormat,
@@ -39,52 +117,36 @@ pub fn build_row_line(
         }
     }
 
-    let marker = if e.is_dir { "/" } else { "" };
-    let name_val = format!("{}{}", e.name, marker);
+    let marker = if e.is_dir && app.config.ui.dir_slash { "/" } else { "" };
+    let mut name_val = format!("{}{}", e.name, marker);
+    if let Some(target) = e.link_target.as_ref()
+    {
+        name_val.push_str(&format!(" -> {}", target.display()));
+    }
     let icon_val = compute_icon(app, e);
-    let info_val = format_info(app, e).unwrap_or_default();
-
-    let mut sel_style = bar_style;
-    if let Some(cb) = app.clipboard.as_ref()
-        && cb.items.iter().any(|p| p == &e.path)
-        && let Some(th) = app.config.ui.theme.as_ref()
+    // toggle:info_column hides the column whatever the info mode
+    let info_val = if app.show_info_column
     {
-        match cb.op
-        {
-            crate::app::ClipboardOp::Copy =>
-            {
-                if let Some(fg) = th
-                    .selection_bar_copy_fg
-                    .as_ref()
-                    .and_then(|s| crate::ui::colors::parse_color(s))
-                {
-                    sel_style = Style::default().fg(fg);
-                }
-                else
-                {
-                    sel_style = Style::default().fg(Color::Green);
-                }
-            }
-            crate::app::ClipboardOp::Move =>
-            {
-                if let Some(fg) = th
-                    .selection_bar_move_fg
-                    .as_ref()
-                    .and_then(|s| crate::ui::colors::parse_color(s))
-                {
-                    sel_style = Style::default().fg(fg);
-                }
-                else
-                {
-                    sel_style = Style::default().fg(Color::Yellow);
-                }
-            }
-        }
+        format_info(app, e).unwrap_or_default()
     }
+    else
+    {
+        String::new()
+    };
 
+    // Clipboard entries show the op's glyph and colours, selected or not
+    let clip_op = app
+        .clipboard
+        .as_ref()
+        .filter(|cb| cb.items.iter().any(|p| p == &e.path))
+        .map(|cb| cb.op);
     let sel = app.selected.contains(&e.path);
-    let indicator = if sel { "┃" } else { " " };
-    spans.push(Span::styled(indicator.to_string(), sel_style));
+    let (indicator, sel_style) = match clip_op
+    {
+        Some(op) => clipboard_indicator(app, op),
+        None => (if sel { "┃" } else { " " }.to_string(), bar_style),
+    };
+    spans.push(Span::styled(indicator, sel_style));
     spans.push(Span::raw(" "));
 
     let mut left_txt = String::new();
@@ -180,7 +242,9 @@ fn compute_icon(
     ic.default_file.clone().unwrap_or_else(|| "📄".to_string())
 }
 
-fn truncate_with_tilde(
+/// Truncate `s` to `max_w` columns, marking the cut with `~`. Whole grapheme
+/// clusters are kept so emoji sequences and combining marks are never split.
+pub fn truncate_with_tilde(
     s: &str,
     max_w: usize,
 ) -> String
@@ -200,20 +264,58 @@ fn truncate_with_tilde(
     }
     let mut out = String::new();
     let mut used = 0usize;
-    for ch in s.chars()
+    for g in s.graphemes(true)
     {
-        let cw = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
-        if used + cw + 1 > max_w
+        let gw = UnicodeWidthStr::width(g);
+        if used + gw + 1 > max_w
         {
             break;
         }
-        out.push(ch);
-        used += cw;
+        out.push_str(g);
+        used += gw;
     }
     out.push('~');
     out
 }
 
+/// Truncate `path` from the left to `max_w` columns, marking the cut with
+/// `~` so the file name stays visible. A name that alone is too wide is cut
+/// from the right like [`truncate_with_tilde`].
+pub fn truncate_path_left(
+    path: &std::path::Path,
+    max_w: usize,
+) -> String
+{
+    let s = path.to_string_lossy();
+    if UnicodeWidthStr::width(s.as_ref()) <= max_w
+    {
+        return s.to_string();
+    }
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if UnicodeWidthStr::width(name.as_str()) + 1 > max_w
+    {
+        return truncate_with_tilde(&name, max_w);
+    }
+    let mut tail: Vec<&str> = Vec::new();
+    let mut used = 0usize;
+    for g in s.graphemes(true).rev()
+    {
+        let gw = UnicodeWidthStr::width(g);
+        if used + gw + 1 > max_w
+        {
+            break;
+        }
+        tail.push(g);
+        used += gw;
+    }
+    let mut out = String::from("~");
+    out.extend(tail.into_iter().rev());
+    out
+}
+
 fn entry_style(
     app: &crate::App,
     e: &crate::app::DirEntryInfo,
@@ -235,8 +337,15 @@ fn entry_style(
     {
         st = st.bg(bg);
     }
+    let mods = |list: &Option<Vec<String>>| {
+        list.as_deref()
+            .map(crate::ui::colors::parse_modifiers)
+            .unwrap_or_default()
+    };
+    st = st.add_modifier(mods(&th.item_mods));
     if e.is_dir
     {
+        st = st.add_modifier(mods(&th.dir_mods));
         if let Some(fg) =
             th.dir_fg.as_ref().and_then(|s| crate::ui::colors::parse_color(s))
         {
@@ -250,6 +359,7 @@ fn entry_style(
     }
     else
     {
+        st = st.add_modifier(mods(&th.file_mods));
         if let Some(fg) =
             th.file_fg.as_ref().and_then(|s| crate::ui::colors::parse_color(s))
         {
@@ -262,6 +372,7 @@ fn entry_style(
         }
         if is_executable(&e.path)
         {
+            st = st.add_modifier(mods(&th.exec_mods));
             if let Some(fg) = th
                 .exec_fg
                 .as_ref()
@@ -280,6 +391,7 @@ fn entry_style(
     }
     if e.name.starts_with('.')
     {
+        st = st.add_modifier(mods(&th.hidden_mods));
         if let Some(fg) = th
             .hidden_fg
             .as_ref()
@@ -295,6 +407,25 @@ fn entry_style(
             st = st.bg(bg);
         }
     }
+    if e.unreadable
+        && let Some(fg) = th
+            .denied_fg
+            .as_ref()
+            .and_then(|s| crate::ui::colors::parse_color(s))
+    {
+        st = st.fg(fg);
+    }
+    if e.is_symlink
+    {
+        // A link whose target metadata cannot be read is broken
+        let fg =
+            if e.unreadable { &th.symlink_broken_fg } else { &th.symlink_fg };
+        if let Some(fg) =
+            fg.as_ref().and_then(|s| crate::ui::colors::parse_color(s))
+        {
+            st = st.fg(fg);
+        }
+    }
     st
 }
 
@@ -355,6 +486,40 @@ pub fn permissions_string(_e: &crate::app::DirEntryInfo) -> String
     "---------".to_string()
 }
 
+/// Permission bits as a four-digit octal mode, e.g. `0644`.
+#[cfg(unix)]
+pub fn permissions_octal(e: &crate::app::DirEntryInfo) -> String
+{
+    use std::os::unix::fs::PermissionsExt;
+    match std::fs::metadata(&e.path)
+    {
+        Ok(meta) => format!("{:04o}", meta.permissions().mode() & 0o7777),
+        Err(_) => "????".to_string(),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn permissions_octal(_e: &crate::app::DirEntryInfo) -> String
+{
+    "----".to_string()
+}
+
+/// Permissions in the form selected by `ui.permissions_octal`.
+pub fn permissions_display(
+    app: &crate::App,
+    e: &crate::app::DirEntryInfo,
+) -> String
+{
+    if app.config.ui.permissions_octal
+    {
+        permissions_octal(e)
+    }
+    else
+    {
+        permissions_string(e)
+    }
+}
+
 #[cfg(unix)]
 fn is_executable(path: &std::path::Path) -> bool
 {
@@ -385,24 +550,31 @@ fn format_info(
         InfoMode::None => None,
         InfoMode::Size =>
         {
-            if e.is_dir
+            let size = if e.is_dir
             {
-                None
+                if !app.config.ui.dir_sizes
+                {
+                    return None;
+                }
+                // Computed in the background; show an ellipsis until ready
+                match app.dir_sizes.get(&e.path, e.mtime)
+                {
+                    Some(n) => n,
+                    None => return Some("…".to_string()),
+                }
             }
             else
             {
-                Some(match app.display_mode
+                e.size
+            };
+            Some(match app.display_mode
+            {
+                crate::app::DisplayMode::Friendly =>
                 {
-                    crate::app::DisplayMode::Friendly =>
-                    {
-                        crate::ui::format::human_size(e.size)
-                    }
-                    crate::app::DisplayMode::Absolute =>
-                    {
-                        format!("{} B", e.size)
-                    }
-                })
-            }
+                    crate::ui::format::human_size(size)
+                }
+                crate::app::DisplayMode::Absolute => format!("{} B", size),
+            })
         }
         InfoMode::Created => match app.display_mode
         {
@@ -426,5 +598,30 @@ fn format_info(
                 e.mtime.map(crate::ui::format::format_time_ago)
             }
         },
+        InfoMode::Entries =>
+        {
+            let count = if e.is_dir
+            {
+                app.entry_counts.get(
+                    &e.path,
+                    e.mtime,
+                    app.config.ui.show_hidden,
+                )
+            }
+            else
+            {
+                None
+            };
+            Some(match (count, app.display_mode)
+            {
+                (None, _) => "-".to_string(),
+                (Some(n), crate::app::DisplayMode::Absolute) => n.to_string(),
+                (Some(1), crate::app::DisplayMode::Friendly) => "1 item".into(),
+                (Some(n), crate::app::DisplayMode::Friendly) =>
+                {
+                    format!("{} items", n)
+                }
+            })
+        }
     }
 }
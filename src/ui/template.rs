@@ -7,6 +7,32 @@ use ratatui::{
     text::Span,
 };
 
+/// Placeholders accepted in header templates. Short forms (`user`, `host`,
+/// `name`, `size`, `perms`) alias the long `username`/`current_file_*` names.
+const HEADER_PLACEHOLDERS: [&str; 21] = [
+    "date",
+    "time",
+    "cwd",
+    "current_file",
+    "current_file_dir",
+    "current_file_name",
+    "name",
+    "username",
+    "user",
+    "hostname",
+    "host",
+    "current_file_permissions",
+    "perms",
+    "current_file_size",
+    "size",
+    "current_file_ctime",
+    "current_file_mtime",
+    "current_file_extension",
+    "owner",
+    "sort",
+    "info",
+];
+
 #[derive(Clone, Default)]
 pub struct HeaderSide
 {
@@ -75,7 +101,7 @@ pub fn format_header_side(
         .unwrap_or_else(|| String::from("-"));
     let perms = sel_opt
         .as_ref()
-        .map(|e| super::panes::permissions_string(e))
+        .map(|e| super::panes::permissions_display(app, e))
         .unwrap_or_else(|| String::from("---------"));
     let size_s = sel_opt
         .as_ref()
@@ -125,28 +151,21 @@ pub fn format_header_side(
         .map(|t| super::panes::format_time_abs(t, date_fmt))
         .unwrap_or_else(|| String::from("-"));
 
+    let sort_s = format!(
+        "{}{}",
+        crate::enums::sort_key_to_str(app.get_sort_key()),
+        if app.get_sort_reverse() { " (rev)" } else { "" }
+    );
+    let info_s = crate::enums::info_mode_to_str(app.get_info_mode())
+        .unwrap_or("none")
+        .to_string();
+
     let tpl = tpl_opt.cloned().unwrap_or_default();
 
-    let allowed = [
-        "date",
-        "time",
-        "cwd",
-        "current_file",
-        "current_file_dir",
-        "current_file_name",
-        "username",
-        "hostname",
-        "current_file_permissions",
-        "current_file_size",
-        "current_file_ctime",
-        "current_file_mtime",
-        "current_file_extension",
-        "owner",
-    ];
     for ph in placeholders_in(&tpl)
     {
         let base = ph.split('|').next().unwrap_or(ph.as_str());
-        if !allowed.contains(&base)
+        if !HEADER_PLACEHOLDERS.contains(&base)
         {
             crate::trace::log(format!(
                 "[header] unknown placeholder '{{{}}}'",
@@ -164,11 +183,13 @@ pub fn format_header_side(
             "cwd" => cwd_s.clone(),
             "current_file" => current_file.clone(),
             "current_file_dir" => current_file_dir.clone(),
-            "current_file_name" => name_now.clone(),
-            "username" => username.clone(),
-            "hostname" => hostname.clone(),
-            "current_file_permissions" => perms.clone(),
-            "current_file_size" => size_s.clone(),
+            "current_file_name" | "name" => name_now.clone(),
+            "username" | "user" => username.clone(),
+            "hostname" | "host" => hostname.clone(),
+            "current_file_permissions" | "perms" => perms.clone(),
+            "current_file_size" | "size" => size_s.clone(),
+            "sort" => sort_s.clone(),
+            "info" => info_s.clone(),
             "current_file_ctime" => ctime_s.clone(),
             "current_file_mtime" => mtime_s.clone(),
             "current_file_extension" => ext.clone(),
@@ -256,23 +277,7 @@ pub fn format_header_side(
                     Some((n, m)) => (n.trim(), Some(m.trim())),
                     None => (token.trim(), None),
                 };
-                let allowed = [
-                    "date",
-                    "time",
-                    "cwd",
-                    "current_file",
-                    "current_file_dir",
-                    "current_file_name",
-                    "username",
-                    "hostname",
-                    "current_file_permissions",
-                    "current_file_size",
-                    "current_file_ctime",
-                    "current_file_mtime",
-                    "current_file_extension",
-                    "owner",
-                ];
-                if allowed.contains(&name)
+                if HEADER_PLACEHOLDERS.contains(&name)
                 {
                     let val = value_for(name);
                     out.text.push_str(&val);
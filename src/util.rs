@@ -69,6 +69,337 @@ pub fn is_binary(path: &Path) -> bool
     false
 }
 
+/// List the entries of a zip, tar, or gzip-compressed tar archive without
+/// extracting it. Each line is `<size>  <name>`; at most `max_entries` lines
+/// are returned. Returns `None` when the extension is not a recognized
+/// archive type.
+pub fn list_archive(
+    path: &Path,
+    max_entries: usize,
+) -> Option<io::Result<Vec<String>>>
+{
+    let name = path.file_name()?.to_string_lossy().to_ascii_lowercase();
+    let format_entry =
+        |size: u64, name: &str| format!("{:>10}  {}", size, name);
+    if name.ends_with(".zip")
+    {
+        return Some((|| {
+            let mut archive = zip::ZipArchive::new(File::open(path)?)
+                .map_err(io::Error::other)?;
+            let mut out = Vec::new();
+            for i in 0..archive.len().min(max_entries)
+            {
+                let entry =
+                    archive.by_index_raw(i).map_err(io::Error::other)?;
+                let entry_name = entry.name().map_err(io::Error::other)?;
+                out.push(format_entry(entry.size(), &entry_name));
+            }
+            Ok(out)
+        })());
+    }
+    let gz = name.ends_with(".tar.gz") || name.ends_with(".tgz");
+    if !gz && !name.ends_with(".tar")
+    {
+        return None;
+    }
+    Some((|| {
+        let file = File::open(path)?;
+        let reader: Box<dyn Read> = if gz
+        {
+            Box::new(flate2::read::GzDecoder::new(file))
+        }
+        else
+        {
+            Box::new(file)
+        };
+        let mut archive = tar::Archive::new(reader);
+        let mut out = Vec::new();
+        for entry in archive.entries()?.take(max_entries)
+        {
+            let entry = entry?;
+            let entry_name = entry.path()?.to_string_lossy().to_string();
+            out.push(format_entry(entry.size(), &entry_name));
+        }
+        Ok(out)
+    })())
+}
+
+/// Quote `s` for the platform shell (`cmd` on Windows, `sh` elsewhere).
+pub fn shell_quote(s: &str) -> String
+{
+    #[cfg(windows)]
+    {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    }
+    #[cfg(not(windows))]
+    {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+/// Replace `{path}`, `{dir}`, `{name}` and `{ext}` in a configured command
+/// with shell-quoted values for `path`.
+pub fn expand_path_placeholders(
+    cmd: &str,
+    path: &Path,
+) -> String
+{
+    expand_command_template(cmd, path, &[])
+}
+
+/// Like [`expand_path_placeholders`], also replacing each `{key}` in `extra`
+/// with its value as is (for numbers such as `{width}`). The template is
+/// scanned once, so placeholder text inside a substituted value (a file
+/// named `{ext}.txt`) is never expanded again.
+pub fn expand_command_template(
+    cmd: &str,
+    path: &Path,
+    extra: &[(&str, String)],
+) -> String
+{
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let name =
+        path.file_name().map(|s| s.to_string_lossy()).unwrap_or_default();
+    let ext = path.extension().map(|s| s.to_string_lossy()).unwrap_or_default();
+    let mut out = String::with_capacity(cmd.len());
+    let mut rest = cmd;
+    while let Some(open) = rest.find('{')
+    {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let key = after.find('}').map(|close| &after[..close]);
+        let value = match key
+        {
+            Some("path") => Some(shell_quote(&path.to_string_lossy())),
+            Some("dir") => Some(shell_quote(&dir.to_string_lossy())),
+            Some("name") => Some(shell_quote(&name)),
+            Some("ext") => Some(shell_quote(&ext)),
+            Some(k) =>
+            {
+                extra.iter().find(|(n, _)| *n == k).map(|(_, v)| v.clone())
+            }
+            None => None,
+        };
+        match (key, value)
+        {
+            (Some(k), Some(v)) =>
+            {
+                out.push_str(&v);
+                rest = &after[k.len() + 1..];
+            }
+            _ =>
+            {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Build an opener template (with a `{path}` placeholder) that starts
+/// `editor` at `line`. The argument form follows the editor's program name:
+/// `--goto {path}:N` for VS Code, `{path}:N` for Sublime Text, Helix and Zed,
+/// `--line N` for TextMate, and `+N` (vi, Vim, Neovim, Emacs, nano, ...)
+/// otherwise.
+pub fn editor_line_command(
+    editor: &str,
+    line: usize,
+) -> String
+{
+    let editor = editor.trim();
+    let program = editor
+        .split_whitespace()
+        .next()
+        .map(|p| {
+            Path::new(p)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_lowercase())
+                .unwrap_or_default()
+        })
+        .unwrap_or_default();
+    match program.as_str()
+    {
+        "code" | "code-insiders" | "codium" =>
+        {
+            format!("{} --goto {{path}}:{}", editor, line)
+        }
+        "subl" | "hx" | "helix" | "zed" =>
+        {
+            format!("{} {{path}}:{}", editor, line)
+        }
+        "mate" => format!("{} --line {} {{path}}", editor, line),
+        _ => format!("{} +{} {{path}}", editor, line),
+    }
+}
+
+/// Expand a leading `~` (alone or followed by a path separator) to the home
+/// directory (`$HOME`, or `%USERPROFILE%` on Windows).
+pub fn expand_tilde(s: &str) -> std::path::PathBuf
+{
+    if let Some(home) = home_dir()
+    {
+        if s == "~"
+        {
+            return std::path::PathBuf::from(home);
+        }
+        if let Some(rest) =
+            s.strip_prefix("~/").or_else(|| s.strip_prefix("~\\"))
+        {
+            return Path::new(&home).join(rest);
+        }
+    }
+    std::path::PathBuf::from(s)
+}
+
+/// `path` relative to `base` when it lies beneath it, otherwise under `~`
+/// when it is inside the home directory, otherwise unchanged.
+pub fn relative_path(
+    path: &Path,
+    base: &Path,
+) -> String
+{
+    if let Ok(rel) = path.strip_prefix(base)
+    {
+        return if rel.as_os_str().is_empty()
+        {
+            ".".to_string()
+        }
+        else
+        {
+            rel.to_string_lossy().to_string()
+        };
+    }
+    if let Some(home) = home_dir()
+        && let Ok(rel) = path.strip_prefix(&home)
+    {
+        return if rel.as_os_str().is_empty()
+        {
+            "~".to_string()
+        }
+        else
+        {
+            format!("~{}{}", std::path::MAIN_SEPARATOR, rel.to_string_lossy())
+        };
+    }
+    path.to_string_lossy().to_string()
+}
+
+pub(crate) fn home_dir() -> Option<String>
+{
+    ["HOME", "USERPROFILE"]
+        .iter()
+        .filter_map(|k| std::env::var(k).ok())
+        .find(|v| !v.trim().is_empty())
+}
+
+/// Complete the last component of `input` to a directory name, resolving
+/// relative input against `cwd`. A unique match gains a trailing `/`;
+/// several matches extend to their common prefix. Dot-directories are only
+/// offered when the component starts with `.`.
+pub fn complete_dir_path(
+    input: &str,
+    cwd: &Path,
+) -> String
+{
+    if input == "~"
+    {
+        return "~/".to_string();
+    }
+    let split = input.rfind(['/', std::path::MAIN_SEPARATOR]).map(|i| i + 1);
+    let (head, prefix) = input.split_at(split.unwrap_or(0));
+    let base = if head.is_empty()
+    {
+        cwd.to_path_buf()
+    }
+    else
+    {
+        let p = expand_tilde(head);
+        if p.is_absolute() { p } else { cwd.join(p) }
+    };
+    let Ok(rd) = std::fs::read_dir(&base)
+    else
+    {
+        return input.to_string();
+    };
+    let mut names: Vec<String> = rd
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|n| n.starts_with(prefix))
+        .filter(|n| prefix.starts_with('.') || !n.starts_with('.'))
+        .collect();
+    names.sort();
+    match names.as_slice()
+    {
+        [] => input.to_string(),
+        [only] => format!("{}{}/", head, only),
+        [first, rest @ ..] =>
+        {
+            let mut common = first.clone();
+            for n in rest
+            {
+                let len = common
+                    .char_indices()
+                    .zip(n.chars())
+                    .find(|((_, a), b)| a != b)
+                    .map_or(common.len().min(n.len()), |((i, _), _)| i);
+                common.truncate(len);
+            }
+            format!("{}{}", head, common)
+        }
+    }
+}
+
+/// Expand `$VAR` and `${VAR}` from the environment. Unset variables and
+/// anything that is not a valid name are left as written so the shell can
+/// still see them in commands.
+pub fn expand_env_vars(s: &str) -> String
+{
+    let is_name = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(pos) = rest.find('$')
+    {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let (name, tail) = if let Some(inner) = after.strip_prefix('{')
+        {
+            match inner.find('}')
+            {
+                Some(end) if inner[..end].chars().all(is_name) =>
+                {
+                    (&inner[..end], &inner[end + 1..])
+                }
+                _ => ("", after),
+            }
+        }
+        else
+        {
+            let end = after.find(|c: char| !is_name(c)).unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        let starts_ok =
+            name.chars().next().is_some_and(|c| !c.is_ascii_digit());
+        match std::env::var(name)
+        {
+            Ok(val) if starts_ok =>
+            {
+                out.push_str(&val);
+                rest = tail;
+            }
+            _ =>
+            {
+                out.push('$');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
 /// Expand tabs, strip carriage returns, and replace control characters with
 /// spaces.
 pub fn sanitize_line(s: &str) -> String
@@ -87,3 +418,78 @@ pub fn sanitize_line(s: &str) -> String
     }
     out
 }
+
+/// Collapse runs of blank (whitespace-only) lines into a single empty line.
+pub fn squeeze_blank_lines(lines: Vec<String>) -> Vec<String>
+{
+    let mut out = Vec::with_capacity(lines.len());
+    let mut prev_blank = false;
+    for line in lines
+    {
+        let blank = line.trim().is_empty();
+        if blank && prev_blank
+        {
+            continue;
+        }
+        prev_blank = blank;
+        out.push(line);
+    }
+    out
+}
+
+/// Highlight `lines` of the file at `path` with syntect, returning ANSI
+/// (24-bit colour) strings suitable for the preview renderer. Returns `None`
+/// when the language is not recognized so callers can keep plain text.
+pub fn highlight_lines(
+    path: &Path,
+    lines: &[String],
+) -> Option<Vec<String>>
+{
+    use std::sync::OnceLock;
+    use syntect::{
+        easy::HighlightLines,
+        highlighting::ThemeSet,
+        parsing::SyntaxSet,
+        util::as_24_bit_terminal_escaped,
+    };
+
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+
+    let ss = SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines);
+    let syntax = ss
+        .find_syntax_by_extension(
+            path.extension().and_then(|e| e.to_str()).unwrap_or(""),
+        )
+        .or_else(|| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| ss.find_syntax_by_extension(n))
+        })
+        .or_else(|| {
+            lines.first().and_then(|l| ss.find_syntax_by_first_line(l))
+        })?;
+    if syntax.name == "Plain Text"
+    {
+        return None;
+    }
+    let theme = THEMES
+        .get_or_init(ThemeSet::load_defaults)
+        .themes
+        .get("base16-ocean.dark")?;
+    let mut hl = HighlightLines::new(syntax, theme);
+    let mut out = Vec::with_capacity(lines.len());
+    for line in lines
+    {
+        let with_nl = format!("{}\n", line);
+        let ranges = hl.highlight_line(&with_nl, ss).ok()?;
+        let mut esc = as_24_bit_terminal_escaped(&ranges, false);
+        if esc.ends_with('\n')
+        {
+            esc.pop();
+        }
+        esc.push_str("\x1b[0m");
+        out.push(esc);
+    }
+    Some(out)
+}
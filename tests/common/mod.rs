@@ -0,0 +1,46 @@
+//! Helpers shared by the integration test binaries.
+// Each test binary compiles this module separately and uses only part of it
+#![allow(dead_code)]
+
+/// Draw `app` once into a `width` x `height` test terminal and return the
+/// buffer.
+pub fn render(
+    app: &mut lsv::App,
+    width: u16,
+    height: u16,
+) -> ratatui::buffer::Buffer
+{
+    let backend = ratatui::backend::TestBackend::new(width, height);
+    let mut term = ratatui::Terminal::new(backend).unwrap();
+    term.draw(|f| lsv::ui::draw(f, app)).unwrap();
+    term.backend().buffer().clone()
+}
+
+/// The rendered screen as one string per row.
+pub fn render_rows(
+    app: &mut lsv::App,
+    width: u16,
+    height: u16,
+) -> Vec<String>
+{
+    rows_of(&render(app, width, height))
+}
+
+/// The rendered screen as a single string, rows concatenated.
+pub fn render_to_string(
+    app: &mut lsv::App,
+    width: u16,
+    height: u16,
+) -> String
+{
+    render_rows(app, width, height).concat()
+}
+
+/// The symbols of `buf`, one string per row.
+pub fn rows_of(buf: &ratatui::buffer::Buffer) -> Vec<String>
+{
+    buf.content()
+        .chunks(buf.area.width as usize)
+        .map(|r| r.iter().map(|c| c.symbol()).collect())
+        .collect()
+}
@@ -1,5 +1,7 @@
 use std::env;
 
+mod common;
+
 // Environment variables are process-global and `std::env::{set_var,
 // remove_var}` are `unsafe` due to potential races across threads. Rust tests
 // run in parallel by default, so guard env mutations with a global mutex.
@@ -45,6 +47,21 @@ fn discover_config_paths_honors_lsv_config_dir()
     assert!(!res.exists);
 }
 
+#[test]
+fn config_paths_report_names_the_chosen_root()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join("conf");
+    std::fs::create_dir_all(&dir).unwrap();
+    let report =
+        with_env("LSV_CONFIG_DIR", Some(dir.to_str().unwrap()), || {
+            lsv::config::config_paths_report(tmp.path()).unwrap()
+        });
+    assert!(report.contains(&format!("{} (LSV_CONFIG_DIR)", dir.display())));
+    assert!(report.contains("(missing)"), "{}", report);
+}
+
 #[test]
 #[cfg(not(windows))]
 fn discover_config_paths_uses_xdg_when_set()
@@ -61,3 +78,235 @@ fn discover_config_paths_uses_xdg_when_set()
     assert_eq!(res.root, xdg.join("lsv"));
     assert_eq!(res.entry, xdg.join("lsv").join("init.lua"));
 }
+
+#[test]
+fn quit_discard_skips_writing_marks()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().expect("tmp");
+    let conf = tmp.path().join("conf");
+    std::fs::create_dir_all(&conf).unwrap();
+    let marks = conf.join("marks");
+    with_env("LSV_CONFIG_DIR", Some(conf.to_str().unwrap()), || {
+        // Marks are written as soon as they are set
+        let mut app = lsv::App::new().expect("app");
+        app.set_cwd(tmp.path());
+        assert!(lsv::dispatch_action(&mut app, "cmd:mark a").unwrap());
+        assert!(marks.is_file());
+        std::fs::remove_file(&marks).unwrap();
+
+        // A write that fails is retried on exit, unless quit with quit!/q!
+        for (quit, written) in [("quit!", false), ("q!", false), ("quit", true)]
+        {
+            std::fs::create_dir(&marks).unwrap();
+            let mut app = lsv::App::new().expect("app");
+            app.set_cwd(tmp.path());
+            assert!(lsv::dispatch_action(&mut app, "cmd:mark a").unwrap());
+            std::fs::remove_dir(&marks).unwrap();
+            assert!(lsv::dispatch_action(&mut app, quit).unwrap());
+            assert!(app.get_quit());
+            app.flush_state();
+            assert_eq!(marks.is_file(), written, "{}", quit);
+        }
+    });
+}
+
+#[test]
+fn config_load_error_is_kept_and_shown_on_startup()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().expect("tmp");
+    let conf = tmp.path().join("conf");
+    std::fs::create_dir_all(&conf).unwrap();
+    std::fs::write(conf.join("init.lua"), "lsv.config({ ui = ").unwrap();
+    with_env("LSV_CONFIG_DIR", Some(conf.to_str().unwrap()), || {
+        let mut app = lsv::App::new().expect("app");
+        let err = app.read_config_error().expect("error kept").to_string();
+        assert!(!err.is_empty());
+        assert!(app.get_show_messages());
+        assert_eq!(lsv::exit::code_for_exit(&app), lsv::exit::CONFIG);
+
+        let screen = common::render_to_string(&mut app, 100, 20);
+        assert!(screen.contains("Config load error"));
+
+        // A successful reload clears it
+        std::fs::write(conf.join("init.lua"), "lsv.config({ ui = {} })")
+            .unwrap();
+        assert!(lsv::dispatch_action(&mut app, "config:reload").unwrap());
+        assert!(app.read_config_error().is_none());
+        assert_eq!(lsv::exit::code_for_exit(&app), 0);
+    });
+}
+
+#[test]
+fn theme_path_expands_env_vars()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let temp = tempfile::tempdir().expect("tempdir");
+    let theme_file = temp.path().join("x.lua");
+    std::fs::write(&theme_file, "return { item_fg = 'white' }\n")
+        .expect("write theme");
+    let code = r#"
+lsv.config({
+  ui = { theme_path = "${LSV_TEST_THEME_DIR}/x.lua" },
+  previewers = { md = "glow $LSV_TEST_THEME_DIR/$UNSET_LSV_VAR {path}" },
+})
+"#;
+    let (cfg, _maps, _eng) = with_env(
+        "LSV_TEST_THEME_DIR",
+        Some(temp.path().to_str().unwrap()),
+        || lsv::config::load_config_from_code(code, None).expect("load config"),
+    );
+
+    assert_eq!(cfg.ui.theme_path.as_deref(), Some(theme_file.as_path()));
+    assert_eq!(
+        cfg.ui.theme.as_ref().and_then(|t| t.item_fg.as_deref()),
+        Some("white")
+    );
+    assert_eq!(
+        cfg.previewers.get("md").map(String::as_str),
+        Some(
+            format!("glow {}/$UNSET_LSV_VAR {{path}}", temp.path().display())
+                .as_str()
+        )
+    );
+}
+
+#[test]
+fn config_reload_keeps_old_config_on_error_and_applies_new()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().expect("tmp");
+    let conf = tmp.path().join("conf");
+    std::fs::create_dir_all(&conf).unwrap();
+    std::fs::write(conf.join("init.lua"), "lsv.config({ ui = {} })").unwrap();
+    with_env("LSV_CONFIG_DIR", Some(conf.to_str().unwrap()), || {
+        let mut app = lsv::App::new().expect("app");
+        assert!(!app.show_hidden());
+
+        // Broken config: old config stays, error lands in messages
+        std::fs::write(conf.join("init.lua"), "this is not lua(").unwrap();
+        let before = app.recent_messages_len();
+        assert!(lsv::dispatch_action(&mut app, "config:reload").unwrap());
+        assert!(!app.show_hidden());
+        assert_eq!(app.get_keymap_action("j"), Some("nav:down".into()));
+        assert!(app.get_show_messages());
+        assert_eq!(app.recent_messages_len(), before + 1);
+
+        // Valid config: new values applied
+        std::fs::write(
+            conf.join("init.lua"),
+            "lsv.config({ ui = { show_hidden = true } })",
+        )
+        .unwrap();
+        assert!(lsv::dispatch_action(&mut app, "config:reload").unwrap());
+        assert!(app.show_hidden());
+    });
+}
+
+#[test]
+fn project_config_search_stops_at_home()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().expect("tmp");
+    let home = tmp.path().join("home");
+    let work = home.join("work/src");
+    std::fs::create_dir_all(&work).unwrap();
+    // Above the home directory: never picked up
+    std::fs::write(tmp.path().join(".lsv.lua"), "").unwrap();
+    with_env("HOME", Some(home.to_str().unwrap()), || {
+        assert_eq!(lsv::config::find_project_config(&work), None);
+        std::fs::write(home.join(".lsv.lua"), "").unwrap();
+        assert_eq!(
+            lsv::config::find_project_config(&work),
+            Some(home.join(".lsv.lua"))
+        );
+    });
+}
+
+#[test]
+fn set_start_dir_is_used_by_next_app()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().expect("tmp");
+    let conf = tmp.path().join("conf");
+    let pinned = tmp.path().join("pinned");
+    std::fs::create_dir_all(&conf).unwrap();
+    std::fs::create_dir_all(&pinned).unwrap();
+    let pinned = pinned.canonicalize().unwrap();
+    // The process cwd is global too: put it back afterwards
+    let old_cwd = env::current_dir().unwrap();
+    let started =
+        with_env("LSV_CONFIG_DIR", Some(conf.to_str().unwrap()), || {
+            let mut app = lsv::App::new().expect("app");
+            app.set_cwd(&pinned);
+            assert!(
+                lsv::dispatch_action(&mut app, "config:set_start_dir").unwrap()
+            );
+            env::set_current_dir(tmp.path()).unwrap();
+            lsv::App::new_at_start_dir().map(|next| next.get_cwd_path())
+        });
+    env::set_current_dir(old_cwd).unwrap();
+    assert_eq!(started.expect("app at start dir"), pinned);
+}
+
+#[test]
+fn new_from_template_copies_the_picked_template()
+{
+    use crossterm::event::{
+        KeyCode,
+        KeyEvent,
+        KeyModifiers,
+    };
+    let press = |app: &mut lsv::App, code: KeyCode| {
+        lsv::input::handle_key(app, KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap();
+    };
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().expect("tmp");
+    let conf = tmp.path().join("conf");
+    let templates = conf.join("templates");
+    std::fs::create_dir_all(&templates).unwrap();
+    std::fs::write(templates.join("LICENSE"), "MIT").unwrap();
+    std::fs::write(templates.join("gitignore"), "target/\n").unwrap();
+    let work = tmp.path().join("work");
+    std::fs::create_dir_all(&work).unwrap();
+    std::fs::write(work.join("a.txt"), "").unwrap();
+
+    with_env("LSV_CONFIG_DIR", Some(conf.to_str().unwrap()), || {
+        let mut app = lsv::App::new().expect("app");
+        app.set_cwd(&work);
+        assert!(
+            lsv::dispatch_action(&mut app, "file:new_from_template").unwrap()
+        );
+        // Sorted without regard to case: gitignore, LICENSE
+        press(&mut app, KeyCode::Enter);
+        // The prompt starts with the template's name
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(
+            std::fs::read_to_string(work.join("gitignore2")).unwrap(),
+            "target/\n"
+        );
+        let sel = app.get_list_selected_index().expect("selection");
+        assert_eq!(
+            app.get_current_entry_name(sel).as_deref(),
+            Some("gitignore2")
+        );
+
+        // An existing file is left alone
+        std::fs::write(work.join("LICENSE"), "mine").unwrap();
+        assert!(
+            lsv::dispatch_action(&mut app, "file:new_from_template").unwrap()
+        );
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(
+            std::fs::read_to_string(work.join("LICENSE")).unwrap(),
+            "mine"
+        );
+        assert!(app.last_message().unwrap_or("").starts_with("Exists"));
+    });
+}
@@ -0,0 +1,51 @@
+use lsv::core::filter::{
+    FilterKind,
+    ListFilter,
+};
+
+#[test]
+fn glob_matches_whole_names()
+{
+    let f = ListFilter::new(FilterKind::Glob, "*.rs", true).expect("glob");
+    assert!(f.matches("main.rs"));
+    assert!(!f.matches("main.rs.bak"));
+    assert!(!f.matches("README.md"));
+
+    let f =
+        ListFilter::new(FilterKind::Glob, "a?[0-9].txt", true).expect("glob");
+    assert!(f.matches("ab1.txt"));
+    assert!(!f.matches("ab.txt"));
+    assert!(!f.matches("abx.txt"));
+
+    let f = ListFilter::new(FilterKind::Glob, "[!.]*", true).expect("glob");
+    assert!(f.matches("visible"));
+    assert!(!f.matches(".hidden"));
+
+    // Regex metacharacters are literal in globs
+    let f = ListFilter::new(FilterKind::Glob, "v1.0+(x)", true).expect("glob");
+    assert!(f.matches("v1.0+(x)"));
+    assert!(!f.matches("v1x0+(x)"));
+}
+
+#[test]
+fn regex_matches_anywhere_and_rejects_invalid_patterns()
+{
+    let f =
+        ListFilter::new(FilterKind::Regex, r"^test_\d+", true).expect("regex");
+    assert!(f.matches("test_12.log"));
+    assert!(!f.matches("my_test_12"));
+
+    assert!(ListFilter::new(FilterKind::Regex, "(unclosed", true).is_err());
+    assert!(ListFilter::new(FilterKind::Glob, "[abc", true).is_err());
+}
+
+#[test]
+fn case_insensitive_filters_ignore_letter_case()
+{
+    let f = ListFilter::new(FilterKind::Glob, "*.md", false).expect("glob");
+    assert!(f.matches("README.MD"));
+    let f = ListFilter::new(FilterKind::Regex, "^read", false).expect("regex");
+    assert!(f.matches("README.md"));
+    let f = ListFilter::new(FilterKind::Glob, "*.md", true).expect("glob");
+    assert!(!f.matches("README.MD"));
+}
@@ -32,3 +32,144 @@ fn copy_move_remove_paths()
     lsv::core::fs_ops::remove_path_all(&c).expect("remove");
     assert!(!c.exists());
 }
+
+#[cfg(unix)]
+#[test]
+fn copy_reports_self_referential_symlink()
+{
+    let tmp = tempfile::tempdir().expect("tmp");
+    let a = tmp.path().join("a");
+    fs::create_dir_all(&a).unwrap();
+    fs::write(a.join("one.txt"), b"ONE").unwrap();
+    std::os::unix::fs::symlink(&a, a.join("loop")).unwrap();
+
+    let err = lsv::core::fs_ops::copy_path_recursive(&a, &tmp.path().join("b"))
+        .expect_err("cycle must be reported");
+    assert!(err.to_string().contains("symlink loop"), "{}", err);
+}
+
+#[test]
+fn dir_size_recursive_sums_nested_files()
+{
+    let tmp = tempfile::tempdir().expect("tmp");
+    let root = tmp.path();
+    let sub = root.join("sub");
+    fs::create_dir_all(&sub).unwrap();
+    fs::write(root.join("a.bin"), vec![0u8; 100]).unwrap();
+    fs::write(sub.join("b.bin"), vec![0u8; 23]).unwrap();
+    assert_eq!(lsv::core::dir_sizes::dir_size_recursive(root), 123);
+    assert_eq!(lsv::core::dir_sizes::dir_size_bounded(root, 10), (123, false));
+    // Two entries at the root, then the walk stops before entering `sub`
+    let (_, cut) = lsv::core::dir_sizes::dir_size_bounded(root, 2);
+    assert!(cut);
+}
+
+#[test]
+fn tree_text_indents_nested_entries()
+{
+    let tmp = tempfile::tempdir().expect("tmp");
+    let root = tmp.path().join("proj");
+    fs::create_dir_all(root.join("src/deep")).unwrap();
+    fs::write(root.join("src/deep/x.rs"), b"x").unwrap();
+    fs::write(root.join("src/main.rs"), b"m").unwrap();
+    fs::write(root.join("README"), b"r").unwrap();
+
+    let text = lsv::core::tree::tree_text(&root, 3, false, 100);
+    let expected = "proj/\n├── src/\n│   ├── deep/\n│   │   └── x.rs\n│   └── \
+                    main.rs\n└── README\n";
+    assert_eq!(text, expected);
+
+    // Depth bound stops before the nested directory's contents
+    let shallow = lsv::core::tree::tree_text(&root, 1, false, 100);
+    assert!(shallow.contains("├── src/\n"));
+    assert!(!shallow.contains("main.rs"));
+}
+
+#[cfg(unix)]
+#[test]
+fn tree_text_does_not_follow_symlinked_directories()
+{
+    let tmp = tempfile::tempdir().expect("tmp");
+    let root = tmp.path().join("proj");
+    fs::create_dir_all(root.join("sub")).unwrap();
+    fs::write(root.join("sub/file.txt"), b"x").unwrap();
+    // Two links back up would loop forever if followed
+    std::os::unix::fs::symlink("..", root.join("sub/up")).unwrap();
+    std::os::unix::fs::symlink("..", root.join("sub/up2")).unwrap();
+
+    let text = lsv::core::tree::tree_text(&root, 5, false, 100);
+    assert_eq!(text.matches("file.txt").count(), 1, "{}", text);
+    assert!(text.contains("up/\n"));
+}
+
+#[test]
+fn next_free_copy_name_skips_taken_suffixes()
+{
+    let temp = tempfile::tempdir().unwrap();
+    let file = temp.path().join("report.txt");
+    fs::write(&file, b"r").unwrap();
+    let dir = temp.path().join("photos.d");
+    fs::create_dir(&dir).unwrap();
+    assert_eq!(
+        lsv::core::fs_ops::next_free_copy_name(&file),
+        temp.path().join("report (1).txt")
+    );
+    fs::write(temp.path().join("report (1).txt"), b"r").unwrap();
+    assert_eq!(
+        lsv::core::fs_ops::next_free_copy_name(&file),
+        temp.path().join("report (2).txt")
+    );
+    // Directories keep their whole name, dots included
+    assert_eq!(
+        lsv::core::fs_ops::next_free_copy_name(&dir),
+        temp.path().join("photos.d (1)")
+    );
+}
+
+#[test]
+fn ensure_note_names_file_from_template_and_creates_it_once()
+{
+    use chrono::TimeZone;
+    let temp = tempfile::tempdir().unwrap();
+    let now = chrono::Local.with_ymd_and_hms(2026, 3, 7, 9, 30, 0).unwrap();
+    let (path, created) =
+        lsv::core::fs_ops::ensure_note(temp.path(), "%Y-%m-%d.md", now)
+            .unwrap();
+    assert!(created);
+    assert_eq!(path, temp.path().join("2026-03-07.md"));
+    fs::write(&path, b"today").unwrap();
+    let (again, created) =
+        lsv::core::fs_ops::ensure_note(temp.path(), "%Y-%m-%d.md", now)
+            .unwrap();
+    assert!(!created);
+    assert_eq!(again, path);
+    assert_eq!(fs::read(&path).unwrap(), b"today");
+    assert!(
+        lsv::core::fs_ops::ensure_note(temp.path(), "%Q", now).is_err(),
+        "invalid template"
+    );
+}
+
+#[test]
+fn touch_path_bumps_times_and_creates_missing_files()
+{
+    let temp = tempfile::tempdir().unwrap();
+    let old = std::time::SystemTime::UNIX_EPOCH
+        + std::time::Duration::from_secs(1_000_000);
+    let file = temp.path().join("old.txt");
+    fs::write(&file, b"keep").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&file)
+        .unwrap()
+        .set_modified(old)
+        .unwrap();
+    assert!(!lsv::core::fs_ops::touch_path(&file).unwrap());
+    let mtime = fs::metadata(&file).unwrap().modified().unwrap();
+    assert!(mtime > old);
+    assert_eq!(fs::read(&file).unwrap(), b"keep");
+
+    let fresh = temp.path().join("fresh.txt");
+    assert!(lsv::core::fs_ops::touch_path(&fresh).unwrap());
+    assert!(fresh.is_file());
+}
@@ -0,0 +1,60 @@
+use std::env;
+
+// Environment variables are process-global; serialize mutations across tests.
+static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[test]
+fn trace_file_path_honors_lsv_trace_file()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let target = tmp.path().join("custom-trace.log");
+    let old = env::var("LSV_TRACE_FILE").ok();
+    unsafe {
+        env::set_var("LSV_TRACE_FILE", &target);
+    }
+    let resolved = lsv::trace::file_path();
+    unsafe {
+        match old
+        {
+            Some(s) => env::set_var("LSV_TRACE_FILE", s),
+            None => env::remove_var("LSV_TRACE_FILE"),
+        }
+    }
+    assert_eq!(resolved, Some(target));
+}
+
+#[test]
+fn trace_log_rotates_past_max_bytes()
+{
+    let _g = ENV_LOCK.lock().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let target = tmp.path().join("trace.log");
+    let vars = ["LSV_TRACE", "LSV_TRACE_FILE", "LSV_TRACE_MAX_BYTES"];
+    let old: Vec<Option<String>> =
+        vars.iter().map(|k| env::var(k).ok()).collect();
+    unsafe {
+        env::set_var("LSV_TRACE", "1");
+        env::set_var("LSV_TRACE_FILE", &target);
+        env::set_var("LSV_TRACE_MAX_BYTES", "200");
+    }
+    for i in 0..20
+    {
+        lsv::trace::log(format!("line {i}"));
+    }
+    unsafe {
+        for (k, v) in vars.iter().zip(old)
+        {
+            match v
+            {
+                Some(s) => env::set_var(k, s),
+                None => env::remove_var(k),
+            }
+        }
+    }
+    let current = std::fs::metadata(&target).unwrap().len();
+    assert!(current <= 200, "log grew to {current} bytes");
+    assert!(tmp.path().join("trace.log.1").exists());
+    let tail = std::fs::read_to_string(&target).unwrap();
+    assert!(tail.contains("line 19"));
+}
@@ -14,7 +14,22 @@ fn parse_color_named_and_hex()
     assert_eq!(parse_color("#00ff00"), Some(Color::Rgb(0, 255, 0)));
     assert_eq!(parse_color("#ABCDEF"), Some(Color::Rgb(0xAB, 0xCD, 0xEF)));
     assert_eq!(parse_color("not-a-color"), None);
-    assert_eq!(parse_color("#123"), None);
+    assert_eq!(parse_color("#1234"), None);
+    assert_eq!(parse_color("#12g"), None);
+}
+
+#[test]
+fn parse_color_short_hex_and_rgb_fn()
+{
+    use lsv::ui::colors::parse_color;
+    let orange = Some(Color::Rgb(255, 136, 0));
+    assert_eq!(parse_color("#ff8800"), orange);
+    assert_eq!(parse_color("#F80"), orange);
+    assert_eq!(parse_color("rgb(255,136,0)"), orange);
+    assert_eq!(parse_color(" RGB( 255 , 136 , 0 ) "), orange);
+    assert_eq!(parse_color("rgb(256,0,0)"), None);
+    assert_eq!(parse_color("rgb(1,2)"), None);
+    assert_eq!(parse_color("rgb(1,2,3,4)"), None);
 }
 
 #[test]
@@ -50,3 +65,16 @@ fn ansi_spans_bold_and_blue_then_unbold()
     let s1_bold = spans[1].style.add_modifier(Modifier::BOLD);
     assert_eq!(s1_bold, spans[0].style);
 }
+
+#[test]
+fn parse_modifiers_combines_names_and_aliases()
+{
+    use lsv::ui::colors::parse_modifiers;
+    let names: Vec<String> =
+        ["Bold", "underline", "reverse", "nope"].map(String::from).to_vec();
+    assert_eq!(
+        parse_modifiers(&names),
+        Modifier::BOLD | Modifier::UNDERLINED | Modifier::REVERSED
+    );
+    assert_eq!(parse_modifiers(&[]), Modifier::empty());
+}
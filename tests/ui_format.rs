@@ -49,3 +49,44 @@ fn format_time_ago_buckets()
     let s = format_time_ago(now - Duration::from_secs(86_400 * 800));
     assert!(s.ends_with("y ago"), "got: {}", s);
 }
+
+#[test]
+fn spinner_frame_cycles_with_tick()
+{
+    use lsv::ui::format::spinner_frame;
+    assert_eq!(spinner_frame(0), "⠋");
+    assert_eq!(spinner_frame(1), "⠙");
+    assert_eq!(spinner_frame(9), "⠏");
+    // Wraps around after the last frame
+    assert_eq!(spinner_frame(10), spinner_frame(0));
+    assert_eq!(spinner_frame(23), spinner_frame(3));
+}
+
+#[test]
+fn truncate_with_tilde_keeps_grapheme_clusters_whole()
+{
+    use lsv::ui::row::truncate_with_tilde;
+    // Family emoji (ZWJ sequence) and a flag, each one grapheme cluster
+    let name = "a👨‍👩‍👧🇯🇵e\u{301}.txt";
+    assert_eq!(truncate_with_tilde(name, 100), name);
+    assert_eq!(truncate_with_tilde(name, 4), "a👨‍👩‍👧~");
+    assert_eq!(truncate_with_tilde(name, 6), "a👨‍👩‍👧🇯🇵~");
+    // The combining accent stays attached to its base letter
+    assert_eq!(truncate_with_tilde("e\u{301}xyz", 2), "e\u{301}~");
+}
+
+#[test]
+fn truncate_path_left_keeps_the_file_name()
+{
+    use lsv::ui::row::truncate_path_left;
+    use std::path::Path;
+    let p = Path::new("/home/user/projects/deep/notes.txt");
+    assert_eq!(
+        truncate_path_left(p, 100),
+        "/home/user/projects/deep/notes.txt"
+    );
+    assert_eq!(truncate_path_left(p, 15), "~deep/notes.txt");
+    assert_eq!(truncate_path_left(p, 10), "~notes.txt");
+    // A name wider than the space is cut from the right instead
+    assert_eq!(truncate_path_left(p, 6), "notes~");
+}
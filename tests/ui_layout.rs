@@ -0,0 +1,29 @@
+use lsv::ui::panes::scrolloff_offset;
+
+#[test]
+fn scrolloff_keeps_cursor_inside_margin()
+{
+    // 100 entries in a 10-row viewport with a margin of 3
+    let (len, height, margin) = (100, 10, 3);
+    let mut off = 0;
+    for sel in 0..len
+    {
+        off = scrolloff_offset(off, sel, len, height, margin);
+        let row = sel - off;
+        let near_end = sel + margin >= len;
+        assert!(row >= margin.min(sel), "sel {sel} at row {row}");
+        assert!(near_end || row + margin < height, "sel {sel} at row {row}");
+    }
+    assert_eq!(off, len - height);
+    // Moving back up scrolls only once the cursor reaches the top margin
+    assert_eq!(scrolloff_offset(90, 95, len, height, margin), 90);
+    assert_eq!(scrolloff_offset(90, 92, len, height, margin), 89);
+}
+
+#[test]
+fn scrolloff_is_clamped_for_short_lists_and_viewports()
+{
+    assert_eq!(scrolloff_offset(0, 4, 5, 10, 3), 0);
+    // A margin larger than half the viewport is reduced to fit
+    assert_eq!(scrolloff_offset(0, 3, 50, 4, 10), 1);
+}
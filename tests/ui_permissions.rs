@@ -0,0 +1,17 @@
+#[test]
+#[cfg(unix)]
+fn permissions_octal_formats_mode()
+{
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().expect("tmp");
+    let file = tmp.path().join("secret.txt");
+    std::fs::write(&file, b"x").unwrap();
+    std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o600))
+        .unwrap();
+
+    let mut app = lsv::App::new().expect("app");
+    app.set_cwd(tmp.path());
+    let entry = app.get_entry(0).expect("entry");
+    assert_eq!(lsv::ui::row::permissions_octal(&entry), "0600");
+}
@@ -1,3 +1,5 @@
+mod common;
+
 use ratatui::style::{
     Color,
     Modifier,
@@ -41,3 +43,69 @@ fn template_styles_placeholder()
     assert!(out.text.contains("pre "));
     assert!(out.text.contains(" post"));
 }
+
+#[test]
+fn template_expands_short_tokens_and_sort_info()
+{
+    use lsv::ui::template::format_header_side;
+    let mut app = lsv::App::new().expect("app");
+    assert!(lsv::dispatch_action(&mut app, "sort:size").unwrap());
+    assert!(lsv::dispatch_action(&mut app, "show:size").unwrap());
+    let tpl = String::from("{user}|{sort}|{info}");
+    let out = format_header_side(&app, Some(&tpl));
+    let user = whoami::username().unwrap_or_default();
+    assert_eq!(out.text, format!("{}|size|size", user));
+}
+
+#[test]
+fn header_summarises_marked_entries()
+{
+    let temp = tempfile::tempdir().expect("tempdir");
+    let dir = temp.path();
+    std::fs::write(dir.join("a.bin"), vec![0u8; 1000]).unwrap();
+    std::fs::write(dir.join("b.bin"), vec![0u8; 500]).unwrap();
+    let mut app = lsv::App::new().expect("app");
+    app.set_cwd(dir);
+    assert!(lsv::dispatch_action(&mut app, "display:absolute").unwrap());
+    for idx in 0..2
+    {
+        app.select_index(idx);
+        lsv::dispatch_action(&mut app, "cmd:toggle_current_selected").unwrap();
+    }
+    let render = |app: &mut lsv::App| common::render_to_string(app, 100, 10);
+    assert!(render(&mut app).contains("2 selected · 1500 B"));
+    lsv::dispatch_action(&mut app, "cmd:clear_selected").unwrap();
+    assert!(!render(&mut app).contains("selected ·"));
+}
+
+#[test]
+fn header_colors_from_config_win_over_theme_title()
+{
+    let code = r#"
+lsv.config({
+  ui = {
+    header = { fg = "yellow", bg = "blue" },
+    theme = { title_fg = "red", title_bg = "green" },
+  },
+})
+"#;
+    let (cfg, _maps, _eng) =
+        lsv::config::load_config_from_code(code, None).expect("load config");
+    assert_eq!(cfg.ui.header_fg.as_deref(), Some("yellow"));
+    assert_eq!(cfg.ui.header_bg.as_deref(), Some("blue"));
+
+    let mut app = lsv::App::new().expect("app");
+    app.set_config(cfg);
+    let buf = common::render(&mut app, 100, 10);
+    let first = &buf.content()[0];
+    assert_eq!(first.fg, Color::Yellow);
+    assert_eq!(first.bg, Color::Blue);
+    // The top-level aliases are read into the same fields
+    let (cfg, _maps, _eng) = lsv::config::load_config_from_code(
+        r#"lsv.config({ ui = { header_fg = "cyan", header_bg = "black" } })"#,
+        None,
+    )
+    .expect("load config");
+    assert_eq!(cfg.ui.header_fg.as_deref(), Some("cyan"));
+    assert_eq!(cfg.ui.header_bg.as_deref(), Some("black"));
+}